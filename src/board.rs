@@ -1,10 +1,21 @@
 use core::fmt::Write;
 
-use crate::{CheckersMove, Piece, Position, RulesError};
-
+use crate::{
+    movegen, zobrist, BoardBuilder, BoardSize, CheckersMove, CompactMove, MoveList, Piece,
+    Position, Rules, RulesError,
+};
+
+/// Checkers board stored as bitmasks indexed by square number (see [`Position`]).
+///
+/// The board also keeps an incrementally updated Zobrist hash of its pieces, see
+/// [`Board::zobrist`], and the [`Rules`] its moves are generated with.
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct Board {
-    pub(crate) grid: [[Option<Piece>; 8]; 8],
+    pub(crate) white: u64,
+    pub(crate) black: u64,
+    pub(crate) kings: u64,
+    pub(crate) hash: u64,
+    pub(crate) rules: Rules,
 }
 
 impl Board {
@@ -12,53 +23,169 @@ impl Board {
     pub fn all_possible_moves(&self, current_white: bool) -> Vec<CheckersMove> {
         let mut moves = Vec::new();
 
-        for piece in self.iter().flatten().flatten() {
-            if piece.is_white == current_white {
-                moves.append(&mut piece.all_possible_moves(self));
+        movegen::legal_moves(self, current_white, &mut |compact, path| {
+            if let Some(old) = self.get_tile(compact.from()) {
+                moves.push(compact.with_path(old, path));
             }
-        }
-
-        if moves.iter().any(|i| !i.captures.is_empty()) {
-            moves.retain(|i| !i.captures.is_empty());
-        }
-
-        // let mut can_capture = false;
-        // for piece in self.iter().flatten().flatten() {
-        //     if piece.is_white == current_white {
-        //         let possible = &mut piece.all_possible_moves(self);
-
-        //         if !can_capture && possible.iter().any(f) {
-        //             moves.retain(f);
-        //             can_capture = true;
-        //         }
-
-        //         if can_capture {
-        //             possible.retain(f);
-        //         }
-        //         moves.append(possible);
-        //     }
-        // }
+        });
 
         moves
     }
 
     #[must_use]
     pub fn legal_moves(&self, current_white: bool) -> Vec<CheckersMove> {
-        let possible_moves = self.all_possible_moves(current_white);
-        let mut legal_moves = Vec::new();
+        self.all_possible_moves(current_white)
+    }
 
-        let captured_any = possible_moves.iter().any(|i| !i.captures.is_empty());
+    /// Fills `list` with all legal moves of `current_white` without allocating.
+    ///
+    /// The list is cleared first, so it can be reused between calls. Moves are the same
+    /// and in the same order as the ones returned by [`Self::legal_moves`].
+    ///
+    /// # Panics
+    ///
+    /// - there are more than [`MoveList::CAPACITY`] legal moves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use checkers_lib::*;
+    /// let board = BoardBuilder::default().build();
+    /// let mut list = MoveList::new();
+    ///
+    /// board.generate_moves(true, &mut list);
+    /// assert_eq!(list.len(), 7);
+    /// ```
+    pub fn generate_moves(&self, current_white: bool, list: &mut MoveList) {
+        list.clear();
+
+        movegen::legal_moves(self, current_white, &mut |compact, _| list.push(compact));
+    }
 
-        if captured_any {
-            for i in possible_moves {
-                if !i.captures.is_empty() {
-                    legal_moves.push(i);
-                }
+    /// Encodes a legal move of `self` into a 64-bit integer, e.g. to key opening books
+    /// and databases, see [`CompactMove::encode`].
+    ///
+    /// Use [`Self::decode_move`] with the same board to get the move back.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Rules(IllegalMove)`][0] if `m` is not a legal move of `self`.
+    ///
+    /// [0]: crate::RulesError::IllegalMove
+    pub fn encode_move(&self, m: &CheckersMove) -> Result<u64, crate::Error> {
+        let mut code = None;
+
+        movegen::legal_moves(self, m.old.is_white, &mut |i, path| {
+            if i.from() == m.old.position && i.with_path(m.old, path) == *m {
+                code = Some(i.encode());
             }
+        });
 
-            legal_moves
-        } else {
-            possible_moves
+        code.ok_or_else(|| RulesError::IllegalMove(m.clone()).into())
+    }
+
+    /// Decodes a legal move of `self` encoded by [`Self::encode_move`].
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Position(OutOfBounds)`][0] if a square of `code` is not on the board,
+    /// - [`Error::Rules(Empty)`][1] if there is no piece on the origin square,
+    /// - [`Error::Rules(IllegalMove)`][2] if the decoded move is not legal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use checkers_lib::*;
+    /// #
+    /// let board = BoardBuilder::default().build();
+    ///
+    /// for m in board.legal_moves(true) {
+    ///     assert_eq!(board.decode_move(board.encode_move(&m)?)?, m);
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [0]: crate::position::Error::OutOfBounds
+    /// [1]: crate::RulesError::Empty
+    /// [2]: crate::RulesError::IllegalMove
+    pub fn decode_move(&self, code: u64) -> Result<CheckersMove, crate::Error> {
+        let compact = CompactMove::decode(code, self.rules.board_size)?;
+        let decoded = compact.expand(self)?;
+
+        let mut legal = false;
+        movegen::legal_moves(self, decoded.old.is_white, &mut |i, _| {
+            legal |= i == compact;
+        });
+
+        if !legal {
+            return Err(RulesError::IllegalMove(decoded).into());
+        }
+
+        Ok(decoded)
+    }
+
+    /// Applies a [`CompactMove`] generated from `self` in-place.
+    ///
+    /// This is the allocation-free counterpart of [`Self::apply_move_unchecked`] and
+    /// likewise results in a jumbled up board if passed a move generated from a
+    /// different board.
+    pub fn apply_compact_move(&mut self, apply: CompactMove) -> MoveUndo {
+        let Some(old) = self.get_tile(apply.from()) else {
+            return MoveUndo { captured_kings: 0 };
+        };
+
+        let undo = MoveUndo {
+            captured_kings: self.kings & apply.captures_mask(),
+        };
+
+        for position in squares(apply.captures_mask(), self.rules.board_size) {
+            self.set_tile(position, None);
+        }
+
+        self.set_tile(apply.from(), None);
+
+        self.set_tile(
+            apply.to(),
+            Some(Piece {
+                is_king: old.is_king || apply.promotes(),
+                is_white: old.is_white,
+                position: apply.to(),
+            }),
+        );
+
+        undo
+    }
+
+    /// Reverts a [`CompactMove`] applied with [`Self::apply_compact_move`].
+    ///
+    /// See [`Self::unapply_move`].
+    pub fn unapply_compact_move(&mut self, apply: CompactMove, undo: MoveUndo) {
+        let Some(new) = self.get_tile(apply.to()) else {
+            return;
+        };
+
+        self.set_tile(apply.to(), None);
+
+        self.set_tile(
+            apply.from(),
+            Some(Piece {
+                is_king: new.is_king && !apply.promotes(),
+                is_white: new.is_white,
+                position: apply.from(),
+            }),
+        );
+
+        for position in squares(apply.captures_mask(), self.rules.board_size) {
+            let captured = Piece {
+                is_king: undo.captured_kings & Self::bit(position) != 0,
+                is_white: !new.is_white,
+                position,
+            };
+
+            self.set_tile(position, Some(captured));
         }
     }
 
@@ -77,23 +204,18 @@ impl Board {
     /// only applied to the correct board.
     ///
     /// [1]: crate::RulesError
-    pub fn apply_move(&mut self, apply: &CheckersMove) -> Result<(), RulesError> {
+    pub fn apply_move(&mut self, apply: &CheckersMove) -> Result<MoveUndo, RulesError> {
         if self.get_tile(apply.old_piece().position).is_none() {
             Err(RulesError::Empty(apply.old_piece().position))?;
-        };
-
-        self.set_tile(apply.new_piece().position, Some(apply.new_piece()));
-
-        self.set_tile(apply.old_piece().position, None);
+        }
 
         for position in &apply.captures {
             if self.get_tile(*position).is_none() {
                 Err(RulesError::Empty(*position))?;
             }
-            self.set_tile(*position, None);
         }
 
-        Ok(())
+        Ok(self.apply_move_unchecked(apply))
     }
 
     /// Applies a [`CheckersMove`] to `self` in-place.
@@ -105,13 +227,73 @@ impl Board {
     ///
     /// - tile at position of moving piece is empty
     ///
-    pub fn apply_move_unchecked(&mut self, apply: &CheckersMove) {
-        self.set_tile(apply.new_piece().position, Some(apply.new_piece()));
+    pub fn apply_move_unchecked(&mut self, apply: &CheckersMove) -> MoveUndo {
+        let mut undo = MoveUndo { captured_kings: 0 };
+
+        for position in &apply.captures {
+            undo.captured_kings |= self.kings & Self::bit(*position);
+            self.set_tile(*position, None);
+        }
 
         self.set_tile(apply.old_piece().position, None);
 
+        self.set_tile(apply.new_piece().position, Some(apply.new_piece()));
+
+        undo
+    }
+
+    /// Reverts a [`CheckersMove`] previously applied to `self` with [`Self::apply_move`]
+    /// or [`Self::apply_move_unchecked`], restoring the captured pieces and the moving
+    /// piece as it was before the move (e.g. before promotion).
+    ///
+    /// `undo` must be the value returned when `apply` was applied, and `self` must not
+    /// have been changed since, otherwise the result is a jumbled up board.
+    ///
+    /// # Errors
+    ///
+    /// - [`RulesError::Empty`] if the tile the piece moved to is empty,
+    /// - [`RulesError::Occupied`] if the tile the piece moved from or one of the captured
+    ///   tiles is occupied.
+    pub fn unapply_move(&mut self, apply: &CheckersMove, undo: MoveUndo) -> Result<(), RulesError> {
+        let (old, new) = (apply.old_piece(), apply.new_piece());
+
+        if self.get_tile(new.position).is_none() {
+            Err(RulesError::Empty(new.position))?;
+        }
+
+        if old.position != new.position && self.get_tile(old.position).is_some() {
+            Err(RulesError::Occupied(old.position))?;
+        }
+
         for position in &apply.captures {
-            self.set_tile(*position, None);
+            if self.get_tile(*position).is_some() {
+                Err(RulesError::Occupied(*position))?;
+            }
+        }
+
+        self.unapply_move_unchecked(apply, undo);
+
+        Ok(())
+    }
+
+    /// Reverts a [`CheckersMove`] without checking that it was applied to `self`.
+    ///
+    /// See [`Self::unapply_move`].
+    pub fn unapply_move_unchecked(&mut self, apply: &CheckersMove, undo: MoveUndo) {
+        let old = apply.old_piece();
+
+        self.set_tile(apply.new_piece().position, None);
+
+        self.set_tile(old.position, Some(old));
+
+        for position in &apply.captures {
+            let captured = Piece {
+                is_king: undo.captured_kings & Self::bit(*position) != 0,
+                is_white: !old.is_white,
+                position: *position,
+            };
+
+            self.set_tile(*position, Some(captured));
         }
     }
 
@@ -138,19 +320,118 @@ impl Board {
         self
     }
 
-    pub fn iter(&self) -> std::slice::Iter<'_, [Option<Piece>; 8]> {
+    /// Returns an iterator over the rows of the board, from top (squares 1..=4 on an 8x8
+    /// board) to bottom (squares 29..=32).
+    ///
+    /// Rows borrow the board, so a `Board` can no longer be iterated by value, only
+    /// through a reference.
+    #[must_use]
+    pub fn iter(&self) -> Rows<'_> {
         self.into_iter()
     }
 
-    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, [Option<Piece>; 8]> {
-        self.into_iter()
+    /// Same as [`Self::iter`], the rows it returns are read-only.
+    #[must_use]
+    #[deprecated(
+        note = "the board is stored as bitmasks, so tiles can't be edited through rows; \
+                use `iter` to read them and `BoardBuilder` or `apply_move` to change them"
+    )]
+    pub fn iter_mut(&mut self) -> Rows<'_> {
+        self.iter()
+    }
+
+    /// Returns an iterator over all pieces on the board in ascending square order.
+    #[must_use]
+    pub fn pieces(&self) -> Pieces<'_> {
+        Pieces {
+            board: self,
+            remaining: self.occupied(),
+        }
+    }
+
+    /// Bitmask of all white pieces, bit `n - 1` corresponding to square `n`.
+    #[must_use]
+    pub fn white_mask(&self) -> u64 {
+        self.white
+    }
+
+    /// Bitmask of all black pieces, bit `n - 1` corresponding to square `n`.
+    #[must_use]
+    pub fn black_mask(&self) -> u64 {
+        self.black
+    }
+
+    /// Bitmask of all kings of both colors, bit `n - 1` corresponding to square `n`.
+    #[must_use]
+    pub fn kings_mask(&self) -> u64 {
+        self.kings
+    }
+
+    /// Bitmask of all occupied squares.
+    #[must_use]
+    pub fn occupied(&self) -> u64 {
+        self.white | self.black
+    }
+
+    /// Rules moves on this board are generated with.
+    #[must_use]
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Returns a copy of `self` played with `rules`.
+    ///
+    /// The pieces are inserted again with [`BoardBuilder::try_insert`], so they are
+    /// validated against the new rules.
+    ///
+    /// # Errors
+    ///
+    /// See [`BoardBuilder::try_insert`].
+    pub fn try_with_rules(&self, rules: Rules) -> Result<Board, crate::Error> {
+        let builder = self.pieces().try_fold(
+            BoardBuilder::empty_with_rules(rules),
+            BoardBuilder::try_insert,
+        )?;
+
+        Ok(builder.build())
+    }
+
+    /// Zobrist hash of the pieces on the board.
+    ///
+    /// The hash is maintained incrementally by every board mutation, so this is free to
+    /// call. Boards with the same pieces always have the same hash.
+    #[must_use]
+    pub fn zobrist(&self) -> u64 {
+        self.hash
+    }
+
+    /// Zobrist hash of the pieces on the board combined with the side to move.
+    ///
+    /// Use this instead of [`Self::zobrist`] to key transposition tables or detect
+    /// repetitions, where the same pieces with a different side to move are a different
+    /// position.
+    #[must_use]
+    pub fn zobrist_with_side(&self, current_white: bool) -> u64 {
+        if current_white {
+            self.hash
+        } else {
+            self.hash ^ zobrist::SIDE
+        }
     }
 
     #[must_use]
     pub fn get_tile(&self, pos: Position) -> Option<Piece> {
-        let (x, y) = pos.into();
+        let bit = Self::bit(pos);
 
-        self.grid[y][x]
+        if self.occupied() & bit == 0 {
+            return None;
+        }
+
+        Some(Piece {
+            is_king: self.kings & bit != 0,
+            is_white: self.white & bit != 0,
+            position: pos,
+        })
     }
 
     /// # Safety
@@ -161,56 +442,201 @@ impl Board {
     ///
     #[must_use]
     pub unsafe fn get_tile_unchecked(&self, pos: Position) -> Piece {
-        let (x, y) = pos.into();
-
-        self.grid[y][x].unwrap_unchecked()
+        self.get_tile(pos).unwrap_unchecked()
     }
 
     pub(crate) fn set_tile(&mut self, pos: Position, piece: Option<Piece>) {
-        let (x, y) = pos.into();
+        let bit = Self::bit(pos);
 
-        self.grid[y][x] = piece;
+        if let Some(old) = self.get_tile(pos) {
+            self.hash ^= zobrist::key(old, pos);
+        }
+
+        self.white &= !bit;
+        self.black &= !bit;
+        self.kings &= !bit;
+
+        if let Some(piece) = piece {
+            if piece.is_white {
+                self.white |= bit;
+            } else {
+                self.black |= bit;
+            }
+
+            if piece.is_king {
+                self.kings |= bit;
+            }
+
+            self.hash ^= zobrist::key(piece, pos);
+        }
+    }
+
+    pub(crate) fn bit(pos: Position) -> u64 {
+        1 << (u8::from(pos) - 1)
     }
 }
 
-impl IntoIterator for Board {
-    type Item = [Option<Piece>; 8];
+/// Returns an iterator over the squares of a board of `size` set in `mask`, in ascending
+/// order.
+pub(crate) fn squares(mut mask: u64, size: BoardSize) -> impl Iterator<Item = Position> {
+    std::iter::from_fn(move || {
+        if mask == 0 {
+            return None;
+        }
 
-    type IntoIter = std::array::IntoIter<[Option<Piece>; 8], 8>;
+        #[allow(clippy::cast_possible_truncation)]
+        let index = mask.trailing_zeros() as u8;
+        mask &= mask - 1;
 
-    fn into_iter(self) -> Self::IntoIter {
-        IntoIterator::into_iter(self.grid)
+        Some(Position::new_unchecked(index + 1, size))
+    })
+}
+
+/// Information needed to revert a move, returned by [`Board::apply_move`] and consumed
+/// by [`Board::unapply_move`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveUndo {
+    captured_kings: u64,
+}
+
+/// Iterator over the rows of a [`Board`], see [`Board::iter`].
+#[derive(Clone)]
+pub struct Rows<'a> {
+    board: &'a Board,
+    size: BoardSize,
+    y: usize,
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = Row<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= usize::from(self.size.rows()) {
+            return None;
+        }
+
+        let row = Row {
+            board: self.board,
+            size: self.size,
+            y: self.y,
+            x: 0,
+        };
+        self.y += 1;
+
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rows = usize::from(self.size.rows());
+        let remaining = rows - self.y.min(rows);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Rows<'_> {}
+
+/// Iterator over the tiles of one row of a [`Board`] from left to right, see [`Rows`].
+///
+/// White tiles are always `None`.
+#[derive(Clone)]
+pub struct Row<'a> {
+    board: &'a Board,
+    size: BoardSize,
+    y: usize,
+    x: usize,
+}
+
+impl Iterator for Row<'_> {
+    type Item = Option<Piece>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.x >= usize::from(self.size.rows()) {
+            return None;
+        }
+
+        let tile = Position::try_from_coords((self.x, self.y), self.size)
+            .ok()
+            .and_then(|pos| self.board.get_tile(pos));
+        self.x += 1;
+
+        Some(tile)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let columns = usize::from(self.size.rows());
+        let remaining = columns - self.x.min(columns);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Row<'_> {}
+
+/// Iterator over the pieces of a [`Board`], see [`Board::pieces`].
+#[derive(Clone)]
+pub struct Pieces<'a> {
+    board: &'a Board,
+    remaining: u64,
+}
+
+impl Iterator for Pieces<'_> {
+    type Item = Piece;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = squares(self.remaining, self.board.rules.board_size).next()?;
+        self.remaining &= self.remaining - 1;
+
+        self.board.get_tile(pos)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining.count_ones() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Pieces<'_> {}
+
+impl std::hash::Hash for Board {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
     }
 }
 
 impl<'a> IntoIterator for &'a Board {
-    type Item = &'a [Option<Piece>; 8];
+    type Item = Row<'a>;
 
-    type IntoIter = std::slice::Iter<'a, [Option<Piece>; 8]>;
+    type IntoIter = Rows<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.grid.iter()
+        Rows {
+            board: self,
+            size: self.rules.board_size,
+            y: 0,
+        }
     }
 }
 
+/// Same as the `&Board` impl, the rows are read-only.
 impl<'a> IntoIterator for &'a mut Board {
-    type Item = &'a mut [Option<Piece>; 8];
+    type Item = Row<'a>;
 
-    type IntoIter = std::slice::IterMut<'a, [Option<Piece>; 8]>;
+    type IntoIter = Rows<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.grid.iter_mut()
+        (&*self).into_iter()
     }
 }
 
 impl core::fmt::Display for Board {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut buf = String::new();
+        let rows = usize::from(self.rules.board_size.rows());
+        let width = rows.to_string().len();
 
         for (num, row) in self.iter().enumerate() {
-            write!(buf, "{} ", self.grid.len() - num)?;
+            write!(buf, "{:>width$} ", rows - num)?;
 
-            for tile in row.iter() {
+            for tile in row {
                 write!(
                     buf,
                     "{} ",
@@ -223,8 +649,8 @@ impl core::fmt::Display for Board {
             writeln!(buf)?;
         }
 
-        write!(buf, "  ")?;
-        for num in 1..=self.grid.len() {
+        write!(buf, "{:width$} ", "")?;
+        for num in 1..=rows {
             write!(buf, "{num} ")?;
         }
         writeln!(buf)?;
@@ -238,3 +664,124 @@ impl core::fmt::Debug for Board {
         core::fmt::Display::fmt(&self, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use crate::BoardBuilder;
+
+    #[test]
+    fn test_masks() {
+        let board = BoardBuilder::default().build();
+
+        assert_eq!(board.black_mask(), 0x0000_0FFF);
+        assert_eq!(board.white_mask(), 0xFFF0_0000);
+        assert_eq!(board.kings_mask(), 0);
+        assert_eq!(board.pieces().len(), 24);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_iter() {
+        let mut board = BoardBuilder::default().build();
+        let rows = |rows: Rows<'_>| rows.map(Iterator::collect).collect::<Vec<Vec<_>>>();
+        let expected = rows(board.iter());
+
+        assert_eq!(expected.len(), 8);
+        assert_eq!(expected[0][1].map(|i| u8::from(i.position)), Some(1));
+        assert_eq!(expected[0][0], None);
+
+        assert_eq!(rows(board.iter_mut()), expected);
+        assert_eq!(rows((&mut board).into_iter()), expected);
+    }
+
+    #[test]
+    fn test_zobrist() -> Result<(), crate::Error> {
+        let board = BoardBuilder::default().build();
+        let moves = board.legal_moves(true);
+        let find = |from: u8, to: u8| {
+            moves
+                .iter()
+                .find(|i| {
+                    u8::from(i.old_piece().position) == from
+                        && u8::from(i.new_piece().position) == to
+                })
+                .unwrap()
+        };
+
+        // Same pieces reached by different routes hash the same
+        let a = board
+            .clone()
+            .applied_move(find(21, 17))?
+            .applied_move(find(24, 20))?;
+        let b = board
+            .clone()
+            .applied_move(find(24, 20))?
+            .applied_move(find(21, 17))?;
+        assert_eq!(a.zobrist(), b.zobrist());
+
+        let rebuilt = a
+            .pieces()
+            .try_fold(BoardBuilder::empty(), BoardBuilder::try_insert)?;
+        assert_eq!(rebuilt.build().zobrist(), a.zobrist());
+
+        assert_ne!(a.zobrist(), board.zobrist());
+        assert_ne!(
+            board.zobrist_with_side(true),
+            board.zobrist_with_side(false)
+        );
+        assert_eq!(Board::default().zobrist(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unapply_move() -> Result<(), crate::Error> {
+        // Black man captures a white king and a white man, promoting on the way
+        let board = BoardBuilder::empty()
+            .try_insert(Piece::new(false, false, 21.try_into()?))?
+            .try_insert(Piece::new(true, true, 25.try_into()?))?
+            .try_insert(Piece::new(false, true, 23.try_into()?))?
+            .build();
+
+        for capture in board.legal_moves(false) {
+            let mut applied = board.clone();
+            let undo = applied.apply_move(&capture)?;
+
+            assert!(
+                applied
+                    .get_tile(capture.new_piece().position)
+                    .unwrap()
+                    .is_king
+            );
+
+            applied.unapply_move(&capture, undo)?;
+            assert_eq!(applied, board);
+
+            applied
+                .unapply_move(&capture, undo)
+                .expect_err("unapplied move twice");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_tile() -> Result<(), crate::Error> {
+        let mut board = Board::default();
+        let king = Piece::new(true, false, 18.try_into()?);
+
+        board.set_tile(king.position, Some(king));
+        assert_eq!(board.get_tile(king.position), Some(king));
+        assert_eq!(board.kings_mask(), 1 << 17);
+
+        board.set_tile(king.position, None);
+        assert_eq!(board, Board::default());
+
+        Ok(())
+    }
+}
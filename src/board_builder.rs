@@ -2,7 +2,7 @@ use core::convert::TryInto;
 
 use itertools::iproduct;
 
-use crate::{Board, Piece, Position, RulesError};
+use crate::{position, Board, Piece, Position, Rules, RulesError};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct BoardBuilder {
@@ -14,13 +14,42 @@ pub struct BoardBuilder {
 impl BoardBuilder {
     #[must_use]
     pub fn empty() -> Self {
+        Self::empty_with_rules(Rules::default())
+    }
+
+    /// Creates a builder of an empty board played with `rules`.
+    #[must_use]
+    pub fn empty_with_rules(rules: Rules) -> Self {
         Self {
-            board: Board::default(),
+            board: Board {
+                rules,
+                ..Board::default()
+            },
             white_pieces: 0,
             black_pieces: 0,
         }
     }
 
+    /// Creates a builder of the initial position of a game played with `rules`.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn initial(rules: Rules) -> Self {
+        let mut builder = Self::empty_with_rules(rules);
+        let size = rules.board_size;
+        let pieces = size.pieces();
+
+        for num in (1..=pieces).chain(size.squares() - pieces + 1..=size.squares()) {
+            // never panics because the initial position is valid
+            let position = Position::try_new(num, size).unwrap();
+
+            builder = builder
+                .try_insert(Piece::new(false, num > pieces, position))
+                .unwrap();
+        }
+
+        builder
+    }
+
     #[must_use]
     pub fn build(self) -> Board {
         self.board
@@ -60,20 +89,30 @@ impl BoardBuilder {
     ///
     /// # Errors
     ///
-    /// - [`Error::Rules(ColorLimit)`][1], if trying to go over color limit of 12 pieces
-    ///   per color,
+    /// - [`Error::Position(OutOfBounds)`][0], if the position of `piece` is on a board of
+    ///   a different size than the one being built,
+    /// - [`Error::Rules(ColorLimit)`][1], if trying to go over color limit of
+    ///   [`BoardSize::pieces`][4] pieces per color (12 on an 8x8 board),
     /// - [`Error::Rules(Occupied)`][2], if trying to insert a piece into an occupied
     ///   tile,
     /// - [`Error::Rules(NotKing)`][3], if trying to insert a non-king piece into a
     ///   promoting tile.
     ///
+    /// [0]: crate::position::Error::OutOfBounds
     /// [1]: crate::RulesError::ColorLimit
     /// [2]: crate::RulesError::Occupied
     /// [3]: crate::RulesError::NotKing
+    /// [4]: crate::BoardSize::pieces
     pub fn try_insert(mut self, piece: Piece) -> Result<Self, crate::Error> {
-        if piece.is_white && self.white_pieces >= 12 {
+        let size = self.board.rules.board_size;
+
+        if piece.position.size() != size {
+            Err(position::Error::OutOfBounds)?;
+        }
+
+        if piece.is_white && self.white_pieces >= size.pieces() {
             Err(RulesError::ColorLimit { is_white: true })?;
-        } else if !piece.is_white && self.black_pieces >= 12 {
+        } else if !piece.is_white && self.black_pieces >= size.pieces() {
             Err(RulesError::ColorLimit { is_white: false })?;
         }
 
@@ -105,7 +144,7 @@ impl BoardBuilder {
     /// [0]: crate::RulesError::Empty
     ///
     pub fn try_remove(mut self, position: Position) -> Result<Self, crate::Error> {
-        let Some(Piece {is_white, ..}) = self.board.get_tile(position) else {
+        let Some(Piece { is_white, .. }) = self.board.get_tile(position) else {
             Err(RulesError::Empty(position))?
         };
 
@@ -131,19 +170,65 @@ impl BoardBuilder {
 }
 
 impl Default for BoardBuilder {
+    /// Initial position of a Russian draughts game, see [`Self::initial`].
     fn default() -> Self {
-        let template: [[u8; 8]; 8] = [
-            [0, 2, 0, 2, 0, 2, 0, 2],
-            [2, 0, 2, 0, 2, 0, 2, 0],
-            [0, 2, 0, 2, 0, 2, 0, 2],
-            [0, 0, 0, 0, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0, 0, 0, 0],
-            [1, 0, 1, 0, 1, 0, 1, 0],
-            [0, 1, 0, 1, 0, 1, 0, 1],
-            [1, 0, 1, 0, 1, 0, 1, 0],
-        ];
+        Self::initial(Rules::default())
+    }
+}
+
+/// Serialized form of a board: its rules and pieces.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BoardRepr {
+    rules: Rules,
+    pieces: Vec<crate::piece::PieceRepr>,
+}
+
+/// Serializes the board being built, see [`Board`]'s `Serialize` impl.
+#[cfg(feature = "serde")]
+impl serde::Serialize for BoardBuilder {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.board.serialize(serializer)
+    }
+}
+
+/// Deserializes a board by inserting its pieces with [`BoardBuilder::try_insert`], so
+/// invalid boards are rejected with the same errors.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BoardBuilder {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let BoardRepr { rules, pieces } = BoardRepr::deserialize(deserializer)?;
+
+        pieces
+            .into_iter()
+            .try_fold(Self::empty_with_rules(rules), |builder, piece| {
+                builder.try_insert(piece.try_into_piece(rules.board_size)?)
+            })
+            .map_err(|err| match std::error::Error::source(&err) {
+                Some(source) => serde::de::Error::custom(source),
+                None => serde::de::Error::custom(err),
+            })
+    }
+}
+
+/// Serializes a board as its rules and the list of its pieces, with positions as square
+/// numbers.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Board {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BoardRepr {
+            rules: self.rules,
+            pieces: self.pieces().map(Into::into).collect(),
+        }
+        .serialize(serializer)
+    }
+}
 
-        BoardBuilder::try_from_template(template).unwrap()
+/// Deserializes a board through [`BoardBuilder`]'s `Deserialize` impl.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        BoardBuilder::deserialize(deserializer).map(BoardBuilder::build)
     }
 }
 
@@ -164,6 +249,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_initial() -> Result<(), crate::Error> {
+        let template = [
+            [0, 2, 0, 2, 0, 2, 0, 2],
+            [2, 0, 2, 0, 2, 0, 2, 0],
+            [0, 2, 0, 2, 0, 2, 0, 2],
+            [0, 0, 0, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0, 0, 0],
+            [1, 0, 1, 0, 1, 0, 1, 0],
+            [0, 1, 0, 1, 0, 1, 0, 1],
+            [1, 0, 1, 0, 1, 0, 1, 0],
+        ];
+
+        assert_eq!(
+            BoardBuilder::default(),
+            BoardBuilder::try_from_template(template)?
+        );
+
+        let rules = crate::Variant::Pool.rules();
+        assert_eq!(BoardBuilder::initial(rules).build().rules(), rules);
+
+        let board = BoardBuilder::initial(crate::Variant::International.rules()).build();
+        assert_eq!(board.black_mask(), (1 << 20) - 1);
+        assert_eq!(board.white_mask(), ((1 << 20) - 1) << 30);
+
+        Ok(())
+    }
+
     #[test]
     fn test_from_template() -> Result<(), crate::Error> {
         let template = [
@@ -177,7 +290,7 @@ mod tests {
             [1, 0, 0, 0, 0, 0, 4, 0],
         ];
 
-        let mut result = vec![[
+        let mut result = vec![vec![
             None,
             Some(Piece {
                 is_king: false,
@@ -195,8 +308,8 @@ mod tests {
                 position: 4.try_into()?,
             }),
         ]];
-        result.extend([[None; 8]; 6]);
-        result.push([
+        result.extend(vec![vec![None; 8]; 6]);
+        result.push(vec![
             Some(Piece {
                 is_king: false,
                 is_white: true,
@@ -216,10 +329,12 @@ mod tests {
         ]);
 
         assert_eq!(
-            BoardBuilder::try_from_template(template)?.build(),
-            Board {
-                grid: result.try_into().unwrap()
-            }
+            BoardBuilder::try_from_template(template)?
+                .build()
+                .iter()
+                .map(Iterator::collect::<Vec<_>>)
+                .collect::<Vec<_>>(),
+            result
         );
 
         Ok(())
@@ -261,6 +376,14 @@ mod tests {
         BoardBuilder::default()
             .try_insert(white)
             .expect_err("tried to insert piece into occupied tile");
+        BoardBuilder::default()
+            .try_insert(black_king)
+            .expect_err("tried to insert piece into occupied tile");
+
+        // Must not place a piece of another board size
+        BoardBuilder::empty_with_rules(crate::Variant::International.rules())
+            .try_insert(white)
+            .expect_err("tried to insert piece of an 8x8 board");
 
         // Must not place non-king piece into promoting tile
         BoardBuilder::empty()
@@ -5,17 +5,56 @@ use crate::Position;
 use std::collections::HashSet;
 use std::fmt::{Debug, Display, Formatter, Write};
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct CheckersMove {
     pub(crate) old: Piece,
     pub(crate) new: Piece,
     pub(crate) captures: HashSet<Position>,
+    /// Squares the piece lands on in order, ending with `new.position`.
+    pub(crate) path: Vec<Position>,
+    /// Captured squares in the order they were jumped over.
+    pub(crate) captures_in_order: Vec<Position>,
 }
 
 impl CheckersMove {
+    /// Creates a move from `old` to `new` capturing `captures`.
+    ///
+    /// The move has no intermediate landing squares, and its captures are ordered by
+    /// square number. Use [`Self::arbitrary_with_path`] to create a multi-jump with its
+    /// full path.
     #[must_use]
     pub fn arbitrary(old: Piece, new: Piece, captures: HashSet<Position>) -> Self {
-        CheckersMove { old, new, captures }
+        let mut captures_in_order = captures.iter().copied().collect::<Vec<_>>();
+        captures_in_order.sort_by_key(|i| u8::from(*i));
+
+        CheckersMove {
+            old,
+            new,
+            captures,
+            path: vec![new.position],
+            captures_in_order,
+        }
+    }
+
+    /// Creates a move from `old` to `new` landing on the squares of `path` in order and
+    /// capturing the squares of `captures` in order.
+    ///
+    /// `path` should end with the position of `new`, and for a capture hold one landing
+    /// square per captured square.
+    #[must_use]
+    pub fn arbitrary_with_path(
+        old: Piece,
+        new: Piece,
+        path: Vec<Position>,
+        captures: Vec<Position>,
+    ) -> Self {
+        CheckersMove {
+            old,
+            new,
+            captures: captures.iter().copied().collect(),
+            path,
+            captures_in_order: captures,
+        }
     }
 
     #[must_use]
@@ -23,6 +62,44 @@ impl CheckersMove {
         &self.captures
     }
 
+    /// Returns the squares the piece lands on in order, ending with the destination.
+    ///
+    /// For a non-capturing move this is just the destination, for a capture it holds
+    /// the square landed on after every jump.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use checkers_lib::*;
+    /// #
+    /// let board = BoardBuilder::empty()
+    ///     .try_insert(Piece::new(false, true, 22.try_into()?))?
+    ///     .try_insert(Piece::new(false, false, 18.try_into()?))?
+    ///     .try_insert(Piece::new(false, false, 11.try_into()?))?
+    ///     .build();
+    /// let capture = &board.legal_moves(true)[0];
+    ///
+    /// assert_eq!(capture.path(), [15.try_into()?, 8.try_into()?]);
+    /// assert_eq!(capture.captures_in_order(), [18.try_into()?, 11.try_into()?]);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn path(&self) -> &[Position] {
+        &self.path
+    }
+
+    /// Returns the captured squares in the order they were jumped over.
+    ///
+    /// Every captured square lies between the previous landing square (or the starting
+    /// square) and the corresponding square of [`Self::path`].
+    #[must_use]
+    pub fn captures_in_order(&self) -> &[Position] {
+        &self.captures_in_order
+    }
+
     #[must_use]
     pub fn old_piece(&self) -> Piece {
         self.old
@@ -33,7 +110,10 @@ impl CheckersMove {
         self.new
     }
 
-    /// Returns a string representation of a move.
+    /// Returns a string representation of a move, with squares converted to `T`.
+    ///
+    /// Captures list every landing square in order, or only the final one if
+    /// `collapsed`. Use [`Notation`][1] for standard notation.
     ///
     /// Also see [`Position`][0]'s `Display` impl.
     ///
@@ -55,6 +135,7 @@ impl CheckersMove {
     /// ```
     ///
     /// [0]: crate::position::Position::fmt
+    /// [1]: crate::notation::Notation
     #[must_use]
     pub fn to_string<T>(&self, collapsed: bool) -> String
     where
@@ -72,34 +153,51 @@ impl CheckersMove {
         }
 
         let mut buf = String::new();
-        write!(buf, "{old:?}x").unwrap();
+        write!(buf, "{old:?}").unwrap();
 
-        for capture in &self.captures {
-            write!(buf, "{:?}x", Into::<T>::into(*capture)).unwrap();
+        for landing in &self.path {
+            write!(buf, "x{:?}", Into::<T>::into(*landing)).unwrap();
         }
 
-        write!(buf, "{new:?}").unwrap();
-
         buf
     }
 }
 
+impl std::hash::Hash for CheckersMove {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.old.hash(state);
+        self.new.hash(state);
+        self.path.hash(state);
+        self.captures_in_order.hash(state);
+    }
+}
+
 impl Debug for CheckersMove {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut builder = crate::BoardBuilder::empty().try_insert(self.old).unwrap();
+        let rules = crate::Rules {
+            board_size: self.old.position.size(),
+            ..crate::Rules::default()
+        };
+        // Place the pieces directly: a builder would reject positions such as
+        // a captured man on its promotion row, and `fmt` must not panic.
+        let mut board_from = crate::Board {
+            rules,
+            ..crate::Board::default()
+        };
+        board_from.set_tile(self.old.position, Some(self.old));
 
         for capture_pos in &self.captures {
-            builder = builder
-                .try_insert(Piece::new(false, !self.old.is_white, *capture_pos))
-                .unwrap();
+            board_from.set_tile(
+                *capture_pos,
+                Some(Piece::new(false, !self.old.is_white, *capture_pos)),
+            );
         }
 
-        let board_from = builder.build();
-
-        let board_to = crate::BoardBuilder::empty()
-            .try_insert(self.new)
-            .unwrap()
-            .build();
+        let mut board_to = crate::Board {
+            rules,
+            ..crate::Board::default()
+        };
+        board_to.set_tile(self.new.position, Some(self.new));
 
         writeln!(f)?;
         writeln!(
@@ -119,8 +217,80 @@ impl Debug for CheckersMove {
     }
 }
 
+/// Writes the move in the default [`Notation`][0], e.g. `22-18` or `22x15x8`.
+///
+/// [0]: crate::notation::Notation
 impl Display for CheckersMove {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_string::<u8>(false))
+        crate::notation::Notation::default().write(f, self)
+    }
+}
+
+/// Serialized form of a move, with the board size its squares are resolved on.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CheckersMoveRepr {
+    #[serde(default)]
+    board_size: crate::BoardSize,
+    old: crate::piece::PieceRepr,
+    new: crate::piece::PieceRepr,
+    path: Vec<u8>,
+    captures: Vec<u8>,
+}
+
+/// Serializes a move as its board size, old and new pieces, landing squares and captured
+/// squares in order, with positions as square numbers.
+#[cfg(feature = "serde")]
+impl serde::Serialize for CheckersMove {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CheckersMoveRepr {
+            board_size: self.old.position.size(),
+            old: self.old.into(),
+            new: self.new.into(),
+            path: self.path.iter().copied().map(u8::from).collect(),
+            captures: self
+                .captures_in_order
+                .iter()
+                .copied()
+                .map(u8::from)
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a move, checking that its squares are on the board and that its path
+/// ends on the square of the new piece.
+///
+/// The move is not checked for legality, see [`Board::legal_moves`][0].
+///
+/// [0]: crate::Board::legal_moves
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CheckersMove {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let repr = CheckersMoveRepr::deserialize(deserializer)?;
+        let size = repr.board_size;
+        let squares = |nums: Vec<u8>| {
+            nums.into_iter()
+                .map(|num| Position::try_new(num, size))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let old = repr.old.try_into_piece(size).map_err(D::Error::custom)?;
+        let new = repr.new.try_into_piece(size).map_err(D::Error::custom)?;
+        let path = squares(repr.path).map_err(D::Error::custom)?;
+        let captures = squares(repr.captures).map_err(D::Error::custom)?;
+
+        if old.is_white != new.is_white {
+            return Err(D::Error::custom("old and new pieces of different colors"));
+        }
+
+        if path.last() != Some(&new.position) {
+            return Err(D::Error::custom("path doesn't end on the new piece"));
+        }
+
+        Ok(CheckersMove::arbitrary_with_path(old, new, path, captures))
     }
 }
@@ -0,0 +1,530 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{
+    Board, Bound, CheckersMove, CompactMove, Evaluator, HandcraftedEvaluator, MoveList,
+    TranspositionEntry, TranspositionTable,
+};
+
+/// Maximum depth of a search in plies, including capture extensions.
+const MAX_PLY: usize = 128;
+
+/// Score of a won position. Wins in `n` plies score `MATE - n`, losses `n - MATE`.
+pub const MATE: i32 = 30_000;
+
+/// Score bound larger than any evaluation.
+const INFINITY: i32 = MATE + 1;
+
+/// How often the clock is read, in nodes.
+const TIME_CHECK_NODES: u64 = 1024;
+
+/// Limits of a search, see [`Engine::search`].
+///
+/// The search stops as soon as any of the limits is reached. Without a depth limit the
+/// search goes on until the node or time limit, or a depth of 128 plies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum depth of the iterative deepening, in plies.
+    pub depth: Option<u32>,
+
+    /// Maximum number of nodes searched.
+    pub nodes: Option<u64>,
+
+    /// Maximum time spent searching.
+    pub time: Option<Duration>,
+}
+
+/// Result of [`Engine::search`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchResult {
+    /// Best move found, `None` if the side to move has no legal moves.
+    pub best_move: Option<CheckersMove>,
+
+    /// Score of the position for the side to move, see [`MATE`].
+    pub score: i32,
+
+    /// Principal variation, the line both sides are expected to play starting with
+    /// [`Self::best_move`].
+    pub pv: Vec<CheckersMove>,
+
+    /// Depth of the last completed iteration.
+    pub depth: u32,
+
+    /// Number of nodes searched.
+    pub nodes: u64,
+}
+
+/// Iterative deepening alpha-beta search (negamax with principal variation search).
+///
+/// Every iteration searches one ply deeper, starting with the principal variation of
+/// the previous one. Captures are mandatory, so positions with a capture to make are
+/// searched further instead of being evaluated. Other positions at the end of the search
+/// are scored by the [`Evaluator`] of the engine, [`HandcraftedEvaluator`] by default.
+///
+/// Searched positions are stored in a [`TranspositionTable`], which is kept between
+/// searches and may be shared with other engines, see [`Self::with_table`].
+///
+/// # Examples
+///
+/// ```
+/// # use checkers_lib::*;
+/// let board = BoardBuilder::default().build();
+/// let limits = Limits {
+///     depth: Some(4),
+///     ..Limits::default()
+/// };
+/// let result = Engine::new().search(&board, true, limits);
+///
+/// assert_eq!(result.depth, 4);
+/// assert!(board.legal_moves(true).contains(&result.best_move.unwrap()));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Engine<E = HandcraftedEvaluator> {
+    evaluator: E,
+    table: Arc<TranspositionTable>,
+    nodes: u64,
+    node_limit: Option<u64>,
+    deadline: Option<Instant>,
+    stopped: bool,
+}
+
+impl Engine {
+    /// Creates an engine with the default [`HandcraftedEvaluator`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<E: Evaluator> Engine<E> {
+    /// Creates an engine scoring positions with `evaluator`.
+    #[must_use]
+    pub fn with_evaluator(evaluator: E) -> Self {
+        Self {
+            evaluator,
+            table: Arc::default(),
+            nodes: 0,
+            node_limit: None,
+            deadline: None,
+            stopped: false,
+        }
+    }
+
+    /// Returns `self` storing searched positions in `table`, e.g. to share it between
+    /// engines searching in parallel.
+    #[must_use]
+    pub fn with_table(self, table: Arc<TranspositionTable>) -> Self {
+        Self { table, ..self }
+    }
+
+    #[must_use]
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    #[must_use]
+    pub fn table(&self) -> &Arc<TranspositionTable> {
+        &self.table
+    }
+
+    /// Searches `board` with `current_white` to move within `limits`.
+    ///
+    /// The result is the one of the last completed iteration. If the search is stopped
+    /// before the first one completes, the best move found so far is returned, or the
+    /// first legal move, so there is a best move whenever there is a legal one.
+    #[must_use]
+    pub fn search(&mut self, board: &Board, current_white: bool, limits: Limits) -> SearchResult {
+        self.nodes = 0;
+        self.node_limit = limits.nodes;
+        self.deadline = limits.time.map(|time| Instant::now() + time);
+        self.stopped = false;
+        self.table.new_search();
+
+        #[allow(clippy::cast_possible_truncation)]
+        let max_depth = limits.depth.unwrap_or(u32::MAX).min(MAX_PLY as u32);
+        let mut board = board.clone();
+
+        let mut best_pv = Vec::new();
+        let mut best_score = 0;
+        let mut completed = 0;
+
+        for depth in 1..=max_depth {
+            let mut pv = Vec::new();
+            let score = self.negamax(
+                &mut board,
+                current_white,
+                depth,
+                0,
+                (-INFINITY, INFINITY),
+                &best_pv,
+                &mut pv,
+            );
+
+            if self.stopped {
+                if completed == 0 && !pv.is_empty() {
+                    best_pv = pv;
+                }
+
+                break;
+            }
+
+            best_pv = pv;
+            best_score = score;
+            completed = depth;
+
+            // a forced win or loss within `depth` plies can't get any shorter
+            if best_pv.is_empty() || is_mate(score) {
+                break;
+            }
+        }
+
+        if best_pv.is_empty() {
+            let mut list = MoveList::new();
+            board.generate_moves(current_white, &mut list);
+            best_pv.extend(list.first());
+
+            if list.is_empty() {
+                best_score = -MATE;
+            }
+        }
+
+        let pv = expand_pv(&board, &best_pv);
+
+        SearchResult {
+            best_move: pv.first().cloned(),
+            score: best_score,
+            pv,
+            depth: completed,
+            nodes: self.nodes,
+        }
+    }
+
+    /// Returns the score of `board` for `current_white` searched `depth` plies deep,
+    /// filling `pv` with the principal variation.
+    ///
+    /// The moves of `follow`, the principal variation of the previous iteration, are
+    /// searched first while the search stays on it.
+    #[allow(clippy::too_many_arguments)]
+    fn negamax(
+        &mut self,
+        board: &mut Board,
+        current_white: bool,
+        depth: u32,
+        ply: usize,
+        (mut alpha, beta): (i32, i32),
+        follow: &[CompactMove],
+        pv: &mut Vec<CompactMove>,
+    ) -> i32 {
+        if self.should_stop() {
+            self.stopped = true;
+            return 0;
+        }
+
+        self.nodes += 1;
+
+        let hash = board.zobrist_with_side(current_white);
+        let entry = self.table.probe(hash, board.rules().board_size);
+
+        // cutoffs are left out of the principal variation search to keep the pv whole
+        if let Some(entry) = entry.filter(|i| beta - alpha == 1 && u32::from(i.depth) >= depth) {
+            let score = score_from_table(entry.score, ply);
+
+            match entry.bound {
+                Bound::Exact => return score,
+                Bound::Lower if score >= beta => return score,
+                Bound::Upper if score <= alpha => return score,
+                _ => {}
+            }
+        }
+
+        let mut list = MoveList::new();
+        board.generate_moves(current_white, &mut list);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let Some(first) = list.first() else {
+            return ply as i32 - MATE;
+        };
+
+        // captures are mandatory, so a position with one isn't quiet enough to evaluate
+        if (depth == 0 && !first.is_capture()) || ply >= MAX_PLY {
+            return self.evaluator.evaluate(board, current_white);
+        }
+
+        let hint = follow
+            .first()
+            .copied()
+            .or(entry.and_then(|i| i.best_move))
+            .filter(|i| list.contains(i));
+        let ordered = hint
+            .into_iter()
+            .chain(list.iter().copied().filter(|i| Some(*i) != hint));
+
+        let mut best = None;
+
+        for (i, compact) in ordered.enumerate() {
+            let child_follow = match follow.split_first() {
+                Some((followed, rest)) if *followed == compact => rest,
+                _ => &[],
+            };
+            let mut child_pv = Vec::new();
+            let mut search = |engine: &mut Self, window: (i32, i32)| {
+                child_pv.clear();
+
+                let undo = board.apply_compact_move(compact);
+                let score = -engine.negamax(
+                    board,
+                    !current_white,
+                    depth.saturating_sub(1),
+                    ply + 1,
+                    window,
+                    child_follow,
+                    &mut child_pv,
+                );
+                board.unapply_compact_move(compact, undo);
+
+                score
+            };
+
+            let score = if i == 0 {
+                search(self, (-beta, -alpha))
+            } else {
+                let score = search(self, (-alpha - 1, -alpha));
+
+                if score > alpha && score < beta && !self.stopped {
+                    search(self, (-beta, -alpha))
+                } else {
+                    score
+                }
+            };
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > alpha {
+                alpha = score;
+                best = Some(compact);
+
+                pv.clear();
+                pv.push(compact);
+                pv.append(&mut child_pv);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = match best {
+            Some(_) if alpha >= beta => Bound::Lower,
+            Some(_) => Bound::Exact,
+            None => Bound::Upper,
+        };
+
+        self.table.store(
+            hash,
+            TranspositionEntry {
+                depth: u8::try_from(depth).unwrap_or(u8::MAX),
+                bound,
+                score: score_to_table(alpha, ply),
+                best_move: best.or(hint),
+            },
+        );
+
+        alpha
+    }
+
+    /// Returns if the node or time limit is reached.
+    fn should_stop(&self) -> bool {
+        if self.stopped || self.node_limit.is_some_and(|limit| self.nodes >= limit) {
+            return true;
+        }
+
+        self.nodes.is_multiple_of(TIME_CHECK_NODES)
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Converts a score found `ply` plies from the root into a score of the position
+/// itself, counting wins and losses from there instead of from the root.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn score_to_table(score: i32, ply: usize) -> i32 {
+    if is_mate(score) {
+        score + score.signum() * ply as i32
+    } else {
+        score
+    }
+}
+
+/// Inverse of [`score_to_table`].
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn score_from_table(score: i32, ply: usize) -> i32 {
+    if is_mate(score) {
+        score - score.signum() * ply as i32
+    } else {
+        score
+    }
+}
+
+/// Returns if `score` is a forced win or loss, see [`MATE`].
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+pub fn is_mate(score: i32) -> bool {
+    score.abs() >= MATE - MAX_PLY as i32
+}
+
+/// Converts the compact moves of `pv` played from `board` into [`CheckersMove`]s.
+fn expand_pv(board: &Board, pv: &[CompactMove]) -> Vec<CheckersMove> {
+    let mut board = board.clone();
+
+    pv.iter()
+        .map_while(|compact| {
+            let expanded = compact.expand(&board).ok()?;
+            board.apply_compact_move(*compact);
+
+            Some(expanded)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use crate::BoardBuilder;
+
+    fn depth_limit(depth: u32) -> Limits {
+        Limits {
+            depth: Some(depth),
+            ..Limits::default()
+        }
+    }
+
+    /// Plain minimax with the same capture extensions as [`Engine::negamax`].
+    fn minimax(board: &mut Board, current_white: bool, depth: u32, ply: usize) -> i32 {
+        let mut list = MoveList::new();
+        board.generate_moves(current_white, &mut list);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let Some(first) = list.first() else {
+            return ply as i32 - MATE;
+        };
+
+        if (depth == 0 && !first.is_capture()) || ply >= MAX_PLY {
+            return HandcraftedEvaluator::default().evaluate(board, current_white);
+        }
+
+        list.iter()
+            .map(|compact| {
+                let undo = board.apply_compact_move(*compact);
+                let score = -minimax(board, !current_white, depth.saturating_sub(1), ply + 1);
+                board.unapply_compact_move(*compact, undo);
+
+                score
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_minimax() -> Result<(), crate::Error> {
+        for fen in [
+            "W:W21-32:B1-12",
+            "B:W18,21,22,23,25,27,28,29,30,31,32:B1,2,3,5,6,7,9,10,11,12,15",
+            "W:WK29,22,23:B6,7,K13,16",
+        ] {
+            let (board, current_white) = Board::from_fen(fen)?;
+
+            for depth in 1..=4 {
+                let result = Engine::new().search(&board, current_white, depth_limit(depth));
+
+                assert_eq!(
+                    result.score,
+                    minimax(&mut board.clone(), current_white, depth, 0),
+                    "{fen} at depth {depth}"
+                );
+                assert_eq!(result.best_move.as_ref(), result.pv.first());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mate() -> Result<(), crate::Error> {
+        let (board, _) = Board::from_fen("W:W22:B18")?;
+        let result = Engine::new().search(&board, true, depth_limit(10));
+
+        assert_eq!(result.score, MATE - 1);
+        assert_eq!(result.depth, 1);
+        assert_eq!(result.pv.len(), 1);
+        assert!(is_mate(result.score));
+
+        let (board, current_white) = Board::from_fen("B:W22:B")?;
+        let result = Engine::new().search(&board, current_white, depth_limit(10));
+
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, -MATE);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_limits() {
+        let board = BoardBuilder::default().build();
+        let legal = board.legal_moves(true);
+
+        let result = Engine::new().search(&board, true, depth_limit(6));
+        assert_eq!(result.depth, 6);
+        assert!(result.pv.len() >= 6);
+
+        let limits = Limits {
+            nodes: Some(1000),
+            ..Limits::default()
+        };
+        let result = Engine::new().search(&board, true, limits);
+        assert_eq!(result.nodes, 1000);
+        assert!(legal.contains(&result.best_move.unwrap()));
+
+        let limits = Limits {
+            time: Some(Duration::ZERO),
+            ..Limits::default()
+        };
+        let result = Engine::new().search(&board, true, limits);
+        assert!(legal.contains(&result.best_move.unwrap()));
+    }
+
+    #[test]
+    fn test_table() -> Result<(), crate::Error> {
+        let (board, current_white) = Board::from_fen("W:WK29,22,23:B6,7,K13,16")?;
+        let mut engine = Engine::new();
+
+        let first = engine.search(&board, current_white, depth_limit(6));
+        let second = engine.search(&board, current_white, depth_limit(6));
+        assert!(second.nodes < first.nodes);
+        assert!(second.best_move.is_some());
+
+        let table = Arc::new(TranspositionTable::new(1 << 12));
+        let board = BoardBuilder::default().build();
+        let legal = board.legal_moves(true);
+
+        std::thread::scope(|scope| {
+            for _ in 0..2 {
+                let mut engine = Engine::new().with_table(Arc::clone(&table));
+                let (board, legal) = (&board, &legal);
+
+                scope.spawn(move || {
+                    let result = engine.search(board, true, depth_limit(5));
+                    assert!(legal.contains(&result.best_move.unwrap()));
+                });
+            }
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{Board, MoveList};
+
+/// Static evaluation of a position, used by [`Engine`][0] at the leaves of its search.
+///
+/// Closures taking a board and the side to move implement the trait too.
+///
+/// # Examples
+///
+/// ```
+/// # use checkers_lib::*;
+/// let pieces = |board: &Board, current_white: bool| {
+///     let (own, other) = if current_white {
+///         (board.white_mask(), board.black_mask())
+///     } else {
+///         (board.black_mask(), board.white_mask())
+///     };
+///
+///     own.count_ones() as i32 - other.count_ones() as i32
+/// };
+/// let board = BoardBuilder::default().build();
+///
+/// let limits = Limits {
+///     depth: Some(4),
+///     ..Limits::default()
+/// };
+/// let result = Engine::with_evaluator(pieces).search(&board, true, limits);
+///
+/// assert_eq!(pieces.evaluate(&board, true), 0);
+/// assert_eq!(result.score, 0);
+/// ```
+///
+/// [0]: crate::Engine
+pub trait Evaluator {
+    /// Scores `board` for `current_white`, positive if the position is better for that
+    /// side.
+    ///
+    /// Scores should stay well below [`MATE`][0] in absolute value.
+    ///
+    /// [0]: crate::MATE
+    fn evaluate(&self, board: &Board, current_white: bool) -> i32;
+}
+
+impl<F: Fn(&Board, bool) -> i32> Evaluator for F {
+    fn evaluate(&self, board: &Board, current_white: bool) -> i32 {
+        self(board, current_white)
+    }
+}
+
+/// Evaluator only counting material, kings being worth three men.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MaterialEvaluator;
+
+impl Evaluator for MaterialEvaluator {
+    fn evaluate(&self, board: &Board, current_white: bool) -> i32 {
+        HandcraftedEvaluator {
+            advancement: 0,
+            back_rank: 0,
+            center: 0,
+            mobility: 0,
+            ..HandcraftedEvaluator::default()
+        }
+        .evaluate(board, current_white)
+    }
+}
+
+/// Default evaluator of [`Engine`][0], summing weighted positional terms.
+///
+/// Every term is counted for both sides and scored as the difference between the side
+/// to move and the other side. Set a weight to zero to disable its term.
+///
+/// [0]: crate::Engine
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HandcraftedEvaluator {
+    /// Value of a man.
+    pub man: i32,
+
+    /// Value of a king.
+    pub king: i32,
+
+    /// Bonus of a man per row it advanced from its back rank.
+    pub advancement: i32,
+
+    /// Bonus of a man still guarding its back rank, keeping the other side from
+    /// promoting there.
+    pub back_rank: i32,
+
+    /// Bonus of a piece on the two middle rows, away from the side edges.
+    pub center: i32,
+
+    /// Bonus per legal move, as generated by [`Board::generate_moves`].
+    pub mobility: i32,
+}
+
+impl Default for HandcraftedEvaluator {
+    fn default() -> Self {
+        Self {
+            man: 100,
+            king: 300,
+            advancement: 3,
+            back_rank: 15,
+            center: 8,
+            mobility: 2,
+        }
+    }
+}
+
+impl Evaluator for HandcraftedEvaluator {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn evaluate(&self, board: &Board, current_white: bool) -> i32 {
+        let rows = usize::from(board.rules().board_size.rows());
+        let middle = rows / 2 - 1..=rows / 2;
+        let inner = 2..rows - 2;
+
+        let mut score = 0;
+
+        for piece in board.pieces() {
+            let (x, y) = piece.position.coords();
+            let mut value = 0;
+
+            if piece.is_king {
+                value += self.king;
+            } else {
+                let advanced = if piece.is_white { rows - 1 - y } else { y };
+
+                value += self.man + self.advancement * advanced as i32;
+
+                if advanced == 0 {
+                    value += self.back_rank;
+                }
+            }
+
+            if middle.contains(&y) && inner.contains(&x) {
+                value += self.center;
+            }
+
+            score += if piece.is_white == current_white {
+                value
+            } else {
+                -value
+            };
+        }
+
+        if self.mobility != 0 {
+            let mut list = MoveList::new();
+
+            board.generate_moves(current_white, &mut list);
+            let own = list.len() as i32;
+
+            board.generate_moves(!current_white, &mut list);
+            let other = list.len() as i32;
+
+            score += self.mobility * (own - other);
+        }
+
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use crate::{BoardBuilder, Variant};
+
+    #[test]
+    fn test_symmetry() -> Result<(), crate::Error> {
+        for variant in Variant::ALL {
+            let board = BoardBuilder::initial(variant.rules()).build();
+
+            assert_eq!(HandcraftedEvaluator::default().evaluate(&board, true), 0);
+            assert_eq!(MaterialEvaluator.evaluate(&board, false), 0);
+        }
+
+        let (board, _) = Board::from_fen("W:WK1,21,22:B5,6")?;
+        let white = HandcraftedEvaluator::default().evaluate(&board, true);
+
+        assert_eq!(
+            HandcraftedEvaluator::default().evaluate(&board, false),
+            -white
+        );
+        assert_eq!(MaterialEvaluator.evaluate(&board, true), 300);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_terms() -> Result<(), crate::Error> {
+        let zero = HandcraftedEvaluator {
+            man: 0,
+            king: 0,
+            advancement: 0,
+            back_rank: 0,
+            center: 0,
+            mobility: 0,
+        };
+
+        // white man advanced 3 rows and in the center against a black man on its back rank
+        let (board, _) = Board::from_fen("W:W18:B1")?;
+
+        let advancement = HandcraftedEvaluator {
+            advancement: 1,
+            ..zero
+        };
+        assert_eq!(advancement.evaluate(&board, true), 3);
+
+        let back_rank = HandcraftedEvaluator {
+            back_rank: 1,
+            ..zero
+        };
+        assert_eq!(back_rank.evaluate(&board, true), -1);
+
+        let center = HandcraftedEvaluator { center: 1, ..zero };
+        assert_eq!(center.evaluate(&board, true), 1);
+
+        let mobility = HandcraftedEvaluator {
+            mobility: 1,
+            ..zero
+        };
+        assert_eq!(mobility.evaluate(&board, true), 0);
+
+        Ok(())
+    }
+}
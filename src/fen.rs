@@ -0,0 +1,235 @@
+//! Draughts FEN, e.g. `W:W21,22,K30:B1,2,3`.
+//!
+//! A FEN holds the side to move followed by the squares of the white and black pieces,
+//! with kings prefixed by `K`. Consecutive squares may be written as a range such as
+//! `1-12`.
+
+use std::fmt::Write;
+
+use crate::{Board, BoardBuilder, BoardSize, Piece, Position, Rules};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid FEN {0:?}")]
+    Syntax(String),
+
+    #[error("invalid color {0:?} in FEN, expected 'W' or 'B'")]
+    Color(String),
+
+    #[error("invalid square {0:?} in FEN")]
+    Square(String),
+}
+
+impl Board {
+    /// Parses a board and the side to move from a FEN string.
+    ///
+    /// Pieces are inserted with [`BoardBuilder::try_insert`], so the same validation
+    /// applies as when building a board by hand.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Fen`][0] if `fen` is malformed,
+    /// - any error of [`BoardBuilder::try_insert`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use checkers_lib::*;
+    /// #
+    /// let (board, current_white) = Board::from_fen("W:W21-32:B1-12")?;
+    ///
+    /// assert_eq!(board, BoardBuilder::default().build());
+    /// assert!(current_white);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [0]: crate::Error::Fen
+    pub fn from_fen(fen: &str) -> Result<(Board, bool), crate::Error> {
+        Self::from_fen_with_rules(fen, Rules::default())
+    }
+
+    /// Parses a board played with `rules` and the side to move from a FEN string.
+    ///
+    /// Squares are numbered according to the board size of `rules`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::from_fen`].
+    pub fn from_fen_with_rules(fen: &str, rules: Rules) -> Result<(Board, bool), crate::Error> {
+        let fen = fen.trim().trim_end_matches('.');
+
+        let mut fields = fen.split(':');
+        let current_white = parse_color(fields.next().unwrap_or_default())?;
+
+        let mut builder = BoardBuilder::empty_with_rules(rules);
+
+        for field in fields {
+            let (color, squares) = field.split_at(field.chars().next().map_or(0, char::len_utf8));
+            let is_white = parse_color(color)?;
+
+            for square in squares.split(',').filter(|i| !i.trim().is_empty()) {
+                let square = square.trim();
+
+                let (is_king, square) = match square.strip_prefix('K') {
+                    Some(square) => (true, square),
+                    None => (false, square),
+                };
+
+                for position in parse_squares(square, rules.board_size)? {
+                    builder = builder.try_insert(Piece {
+                        is_king,
+                        is_white,
+                        position,
+                    })?;
+                }
+            }
+        }
+
+        Ok((builder.build(), current_white))
+    }
+
+    /// Returns the FEN of `self` with `current_white` to move.
+    ///
+    /// Squares are listed in ascending order without ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use checkers_lib::*;
+    /// let board = BoardBuilder::default().build();
+    ///
+    /// assert_eq!(
+    ///     board.to_fen(false),
+    ///     "B:W21,22,23,24,25,26,27,28,29,30,31,32:B1,2,3,4,5,6,7,8,9,10,11,12"
+    /// );
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_fen(&self, current_white: bool) -> String {
+        let mut buf = String::from(if current_white { "W" } else { "B" });
+
+        for is_white in [true, false] {
+            buf.push_str(if is_white { ":W" } else { ":B" });
+
+            let squares = self
+                .pieces()
+                .filter(|i| i.is_white == is_white)
+                .map(|i| format!("{}{}", if i.is_king { "K" } else { "" }, i.position))
+                .collect::<Vec<_>>();
+
+            // writing into a `String` never fails
+            write!(buf, "{}", squares.join(",")).unwrap();
+        }
+
+        buf
+    }
+}
+
+fn parse_color(color: &str) -> Result<bool, Error> {
+    match color.trim() {
+        "W" | "w" => Ok(true),
+        "B" | "b" => Ok(false),
+        "" => Err(Error::Syntax(color.to_owned())),
+        _ => Err(Error::Color(color.to_owned())),
+    }
+}
+
+/// Parses a single square number or an inclusive range of them of a board of `size`.
+fn parse_squares(squares: &str, size: BoardSize) -> Result<Vec<Position>, Error> {
+    let parse = |square: &str| {
+        square
+            .parse::<u8>()
+            .ok()
+            .and_then(|num| Position::try_new(num, size).ok())
+            .ok_or_else(|| Error::Square(square.to_owned()))
+    };
+
+    let Some((first, last)) = squares.split_once('-') else {
+        return Ok(vec![parse(squares)?]);
+    };
+
+    let (first, last) = (u8::from(parse(first)?), u8::from(parse(last)?));
+
+    if first > last {
+        return Err(Error::Square(squares.to_owned()));
+    }
+
+    (first..=last)
+        .map(|i| Position::try_new(i, size).map_err(|_| Error::Square(squares.to_owned())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn test_from_fen() -> Result<(), crate::Error> {
+        let (board, current_white) = Board::from_fen("B:W21,22,K30:BK1,2,3.")?;
+
+        let result = BoardBuilder::empty()
+            .try_insert(Piece::new(false, true, 21.try_into()?))?
+            .try_insert(Piece::new(false, true, 22.try_into()?))?
+            .try_insert(Piece::new(true, true, 30.try_into()?))?
+            .try_insert(Piece::new(true, false, 1.try_into()?))?
+            .try_insert(Piece::new(false, false, 2.try_into()?))?
+            .try_insert(Piece::new(false, false, 3.try_into()?))?
+            .build();
+
+        assert_eq!(board, result);
+        assert!(!current_white);
+
+        assert_eq!(Board::from_fen("W:W:B")?.0, Board::default());
+        assert_eq!(
+            Board::from_fen("W:B1-12:W21-32")?.0,
+            BoardBuilder::default().build()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_fen_invalid() {
+        let invalid = [
+            "",
+            "X:W21:B1",
+            "W:W21:X1",
+            "W:W21,33:B1",
+            "W:W22-21:B1",
+            "W:W21,21:B1",
+            "W:W1:B2",
+            "W:W13-25:B1",
+            "W:é1:B2",
+            "W:Wc3:B1",
+            "W:W21-c3:B1",
+        ];
+
+        for fen in invalid {
+            Board::from_fen(fen).expect_err(fen);
+        }
+    }
+
+    #[test]
+    fn test_to_fen() -> Result<(), crate::Error> {
+        let fen = "W:W21,22,K30:BK1,2,3";
+        let (board, current_white) = Board::from_fen(fen)?;
+
+        assert_eq!(board.to_fen(current_white), fen);
+        assert_eq!(Board::default().to_fen(true), "W:W:B");
+
+        let rules = crate::Variant::International.rules();
+        let fen = "W:W31-50:B1-20";
+        let (board, _) = Board::from_fen_with_rules(fen, rules)?;
+
+        assert_eq!(board, BoardBuilder::initial(rules).build());
+        Board::from_fen(fen).expect_err("parsed 10x10 squares on an 8x8 board");
+
+        Ok(())
+    }
+}
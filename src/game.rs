@@ -0,0 +1,563 @@
+use crate::{Board, BoardBuilder, CheckersMove, MoveList, MoveUndo, Position, Rules, RulesError};
+
+/// Result of a [`Game`], see [`Game::status`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// The side to move has legal moves.
+    Ongoing,
+
+    /// The side to move has no legal moves (or no pieces) left, so the other side won.
+    Win { is_white: bool },
+
+    /// The game is drawn by one of the draw rules of its [`Rules`].
+    Draw(DrawReason),
+}
+
+/// Draw rule that ended a [`Game`], see [`Status::Draw`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DrawReason {
+    /// The position occurred for the third time, see [`Rules::repetition_draw`].
+    Repetition,
+
+    /// Only kings moved without capturing for too long, see [`Rules::king_moves_draw`].
+    KingMoves,
+
+    /// A Russian endgame wasn't won in time, see [`Rules::endgame_draws`].
+    Endgame,
+}
+
+/// Russian endgame a position belongs to, see [`Rules::endgame_draws`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Endgame {
+    /// Number of kings of the side with more pieces.
+    stronger: u32,
+
+    /// Number of kings of the side with fewer pieces.
+    weaker: u32,
+}
+
+impl Endgame {
+    /// Returns the endgame `board` is in, if both sides only have kings.
+    fn of(board: &Board) -> Option<Self> {
+        let men = board.occupied() & !board.kings_mask();
+        let white = board.white_mask().count_ones();
+        let black = board.black_mask().count_ones();
+
+        if men != 0 || white == 0 || black == 0 {
+            return None;
+        }
+
+        Some(Endgame {
+            stronger: white.max(black),
+            weaker: white.min(black),
+        })
+    }
+
+    /// Number of moves of each side after which the endgame of `board` is drawn, if it
+    /// is drawn at all.
+    fn moves(self, board: &Board) -> Option<usize> {
+        match (self.stronger, self.weaker) {
+            (3.., 1) if Self::lone_king_on_main_road(board) => Some(15),
+            (1 | 2, 1) => Some(5),
+            (2, 2) | (3, 1..=2) | (4, 1) => Some(30),
+            (3, 3) | (4, 2..=3) | (5, 1..=2) | (6, 1) => Some(60),
+            _ => None,
+        }
+    }
+
+    /// Returns if the kings of the side with fewer pieces are all on the main road.
+    fn lone_king_on_main_road(board: &Board) -> bool {
+        let lone = if board.white_mask().count_ones() < board.black_mask().count_ones() {
+            board.white_mask()
+        } else {
+            board.black_mask()
+        };
+
+        crate::board::squares(lone, board.rules().board_size).all(Position::is_main_diagonal)
+    }
+}
+
+/// A game in progress: a [`Board`] together with the side to move and the moves played
+/// so far.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # use checkers_lib::*;
+/// #
+/// let mut game = Game::new(Rules::default());
+/// let first = game.board().parse_move("22-18", true)?;
+///
+/// game.play(&first)?;
+///
+/// assert!(!game.current_white());
+/// assert_eq!(game.history(), [first]);
+/// assert_eq!(game.status(), Status::Ongoing);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    start: Board,
+    start_white: bool,
+    board: Board,
+    current_white: bool,
+    history: Vec<CheckersMove>,
+    undos: Vec<MoveUndo>,
+    /// [`Board::zobrist_with_side`] of every position of the game, starting with `start`.
+    positions: Vec<u64>,
+}
+
+impl Game {
+    /// Creates a game from the initial position of `rules`.
+    #[must_use]
+    pub fn new(rules: Rules) -> Self {
+        Self::from_position(
+            BoardBuilder::initial(rules).build(),
+            rules.white_moves_first,
+        )
+    }
+
+    /// Creates a game starting from `board` with `current_white` to move.
+    #[must_use]
+    pub fn from_position(board: Board, current_white: bool) -> Self {
+        Self {
+            start: board.clone(),
+            start_white: current_white,
+            positions: vec![board.zobrist_with_side(current_white)],
+            board,
+            current_white,
+            history: Vec::new(),
+            undos: Vec::new(),
+        }
+    }
+
+    /// Position the game started from.
+    #[must_use]
+    pub fn start(&self) -> &Board {
+        &self.start
+    }
+
+    /// Side to move in the starting position.
+    #[must_use]
+    pub fn start_white(&self) -> bool {
+        self.start_white
+    }
+
+    /// Current position.
+    #[must_use]
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Side to move in the current position.
+    #[must_use]
+    pub fn current_white(&self) -> bool {
+        self.current_white
+    }
+
+    /// Moves played so far, in order.
+    #[must_use]
+    pub fn history(&self) -> &[CheckersMove] {
+        &self.history
+    }
+
+    /// Legal moves of the side to move in the current position.
+    #[must_use]
+    pub fn legal_moves(&self) -> Vec<CheckersMove> {
+        self.board.legal_moves(self.current_white)
+    }
+
+    /// Plays `checkers_move` for the side to move.
+    ///
+    /// # Errors
+    ///
+    /// - [`RulesError::IllegalMove`] if `checkers_move` is not one of the
+    ///   [`Self::legal_moves`], e.g. because the game is over.
+    pub fn play(&mut self, checkers_move: &CheckersMove) -> Result<(), RulesError> {
+        if !self.legal_moves().contains(checkers_move) {
+            return Err(RulesError::IllegalMove(checkers_move.clone()));
+        }
+
+        let undo = self.board.apply_move_unchecked(checkers_move);
+
+        self.undos.push(undo);
+        self.history.push(checkers_move.clone());
+        self.current_white = !self.current_white;
+        self.positions
+            .push(self.board.zobrist_with_side(self.current_white));
+
+        Ok(())
+    }
+
+    /// Takes back the last move played, returning it, or `None` if no move was played.
+    pub fn undo(&mut self) -> Option<CheckersMove> {
+        let (checkers_move, undo) = (self.history.pop()?, self.undos.pop()?);
+
+        self.board.unapply_move_unchecked(&checkers_move, undo);
+        self.current_white = !self.current_white;
+        self.positions.pop();
+
+        Some(checkers_move)
+    }
+
+    /// Returns if the game is still going on, who won it or why it is drawn.
+    ///
+    /// A side without legal moves loses even if a draw rule applies as well.
+    #[must_use]
+    pub fn status(&self) -> Status {
+        let mut list = MoveList::new();
+        self.board.generate_moves(self.current_white, &mut list);
+
+        let rules = self.board.rules();
+
+        if list.is_empty() {
+            Status::Win {
+                is_white: !self.current_white,
+            }
+        } else if rules.repetition_draw && self.repetitions() >= 3 {
+            Status::Draw(DrawReason::Repetition)
+        } else if rules
+            .king_moves_draw
+            .is_some_and(|moves| self.king_moves() >= usize::from(moves) * 2)
+        {
+            Status::Draw(DrawReason::KingMoves)
+        } else if rules.endgame_draws
+            && self
+                .endgame_moves()
+                .is_some_and(|(limit, moves)| moves >= limit * 2)
+        {
+            Status::Draw(DrawReason::Endgame)
+        } else {
+            Status::Ongoing
+        }
+    }
+
+    /// Returns the number of moves of each side after which the Russian endgame of the
+    /// current position is drawn, and the number of moves (of either side) it has lasted
+    /// for.
+    ///
+    /// Moves are counted since the material of both sides last changed, wherever the
+    /// kings moved in between.
+    fn endgame_moves(&self) -> Option<(usize, usize)> {
+        let endgame = Endgame::of(&self.board)?;
+        let limit = endgame.moves(&self.board)?;
+        let mut board = self.board.clone();
+        let mut moves = 0;
+
+        for (checkers_move, undo) in self.history.iter().zip(&self.undos).rev() {
+            board.unapply_move_unchecked(checkers_move, *undo);
+
+            if Endgame::of(&board) != Some(endgame) {
+                break;
+            }
+
+            moves += 1;
+        }
+
+        Some((limit, moves))
+    }
+
+    /// Number of times the current position occurred in the game, with the same side to
+    /// move.
+    #[must_use]
+    pub fn repetitions(&self) -> usize {
+        let current = self.board.zobrist_with_side(self.current_white);
+
+        self.positions.iter().filter(|i| **i == current).count()
+    }
+
+    /// Number of moves (of either side) since the last capture or man move, or since the
+    /// start of the game.
+    #[must_use]
+    pub fn king_moves(&self) -> usize {
+        self.history
+            .iter()
+            .rev()
+            .take_while(|i| i.captures().is_empty() && i.old_piece().is_king)
+            .count()
+    }
+}
+
+impl Default for Game {
+    /// Game from the initial position of Russian draughts.
+    fn default() -> Self {
+        Self::new(Rules::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use crate::{Piece, Variant};
+
+    #[test]
+    fn test_play() -> Result<(), crate::Error> {
+        let mut game = Game::new(Variant::English.rules());
+        assert!(!game.current_white());
+
+        let white = game.board().legal_moves(true);
+        game.play(&white[0])
+            .expect_err("played a move of the side not to move");
+
+        let first = game.board().parse_move("11-15", false)?;
+        game.play(&first)?;
+        let second = game.board().parse_move("22-18", true)?;
+        game.play(&second)?;
+
+        assert_eq!(game.history(), [first.clone(), second.clone()]);
+        assert!(!game.current_white());
+
+        assert_eq!(game.undo(), Some(second));
+        assert_eq!(game.undo(), Some(first));
+        assert_eq!(game.undo(), None);
+        assert_eq!(game.board(), game.start());
+        assert_eq!(game.current_white(), game.start_white());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_status() -> Result<(), crate::Error> {
+        let board = BoardBuilder::empty()
+            .try_insert(Piece::new(false, true, 22.try_into()?))?
+            .try_insert(Piece::new(false, false, 18.try_into()?))?
+            .build();
+        let mut game = Game::from_position(board, true);
+
+        assert_eq!(game.status(), Status::Ongoing);
+
+        // Capturing the last black piece wins
+        game.play(&game.legal_moves()[0])?;
+        assert_eq!(game.status(), Status::Win { is_white: true });
+        assert!(game.legal_moves().is_empty());
+
+        // A blocked side loses as well
+        let board = BoardBuilder::empty()
+            .try_insert(Piece::new(false, true, 29.try_into()?))?
+            .try_insert(Piece::new(false, false, 25.try_into()?))?
+            .try_insert(Piece::new(false, false, 22.try_into()?))?
+            .build();
+        let game = Game::from_position(board, true);
+
+        assert_eq!(game.status(), Status::Win { is_white: false });
+
+        Ok(())
+    }
+
+    fn setup_kings(rules: Rules) -> Result<Game, crate::Error> {
+        let board = BoardBuilder::empty_with_rules(rules)
+            .try_insert(Piece::new(true, true, 30.try_into()?))?
+            .try_insert(Piece::new(true, false, 1.try_into()?))?
+            .try_insert(Piece::new(false, false, 9.try_into()?))?
+            .build();
+
+        Ok(Game::from_position(board, true))
+    }
+
+    #[test]
+    fn test_repetition() -> Result<(), crate::Error> {
+        let mut game = setup_kings(Rules::default())?;
+
+        for _ in 0..2 {
+            for notation in ["30-26", "1-5", "26-30", "5-1"] {
+                assert_eq!(game.status(), Status::Ongoing);
+
+                let checkers_move = game.board().parse_move(notation, game.current_white())?;
+                game.play(&checkers_move)?;
+            }
+        }
+
+        assert_eq!(game.repetitions(), 3);
+        assert_eq!(game.status(), Status::Draw(DrawReason::Repetition));
+
+        game.undo();
+        assert_eq!(game.status(), Status::Ongoing);
+
+        let mut game = setup_kings(Rules {
+            repetition_draw: false,
+            ..Rules::default()
+        })?;
+        for notation in ["30-26", "1-5", "26-30", "5-1"].repeat(2) {
+            let checkers_move = game.board().parse_move(notation, game.current_white())?;
+            game.play(&checkers_move)?;
+        }
+        assert_eq!(game.status(), Status::Ongoing);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_king_moves() -> Result<(), crate::Error> {
+        let rules = Rules {
+            repetition_draw: false,
+            king_moves_draw: Some(2),
+            ..Rules::default()
+        };
+        let mut game = setup_kings(rules)?;
+
+        for notation in ["30-26", "9-13", "26-30", "1-5", "30-26"] {
+            let checkers_move = game.board().parse_move(notation, game.current_white())?;
+            game.play(&checkers_move)?;
+            assert_eq!(game.status(), Status::Ongoing);
+        }
+
+        // The man move reset the count, so it takes two more moves of each side
+        assert_eq!(game.king_moves(), 3);
+
+        let checkers_move = game.board().parse_move("5-1", false)?;
+        game.play(&checkers_move)?;
+
+        assert_eq!(game.king_moves(), 4);
+        assert_eq!(game.status(), Status::Draw(DrawReason::KingMoves));
+
+        Ok(())
+    }
+
+    fn setup_endgame(squares: &[(u8, bool)], rules: Rules) -> Result<Board, crate::Error> {
+        let builder = squares.iter().try_fold(
+            BoardBuilder::empty_with_rules(rules),
+            |builder, &(num, is_white)| {
+                builder.try_insert(Piece::new(true, is_white, num.try_into()?))
+            },
+        )?;
+
+        Ok(builder.build())
+    }
+
+    #[test]
+    fn test_endgame() -> Result<(), crate::Error> {
+        let rules = Rules::default();
+        let moves = |squares: &[(u8, bool)]| -> Result<Option<usize>, crate::Error> {
+            let board = setup_endgame(squares, rules)?;
+            Ok(Endgame::of(&board).and_then(|endgame| endgame.moves(&board)))
+        };
+
+        // lone king on the main road
+        assert_eq!(
+            moves(&[(29, true), (1, false), (5, false), (2, false)])?,
+            Some(15)
+        );
+        assert_eq!(
+            moves(&[(29, true), (1, false), (5, false), (2, false), (3, false)])?,
+            Some(15)
+        );
+
+        assert_eq!(moves(&[(30, true), (1, false)])?, Some(5));
+        assert_eq!(moves(&[(30, true), (1, false), (5, false)])?, Some(5));
+        assert_eq!(
+            moves(&[(30, true), (1, false), (5, false), (2, false)])?,
+            Some(30)
+        );
+        assert_eq!(
+            moves(&[(30, true), (31, true), (5, false), (2, false)])?,
+            Some(30)
+        );
+        assert_eq!(
+            moves(&[(30, true), (31, true), (1, false), (5, false), (2, false)])?,
+            Some(30)
+        );
+        assert_eq!(
+            moves(&[
+                (30, true),
+                (31, true),
+                (32, true),
+                (1, false),
+                (5, false),
+                (2, false)
+            ])?,
+            Some(60)
+        );
+        assert_eq!(
+            moves(&[
+                (30, true),
+                (1, false),
+                (5, false),
+                (2, false),
+                (3, false),
+                (6, false),
+                (7, false)
+            ])?,
+            Some(60)
+        );
+
+        // men on the board, or no endgame rule for 8 pieces
+        let man = BoardBuilder::empty()
+            .try_insert(Piece::new(true, true, 30.try_into()?))?
+            .try_insert(Piece::new(false, false, 9.try_into()?))?
+            .build();
+        assert_eq!(Endgame::of(&man), None);
+        assert_eq!(
+            moves(&[
+                (30, true),
+                (31, true),
+                (32, true),
+                (27, true),
+                (1, false),
+                (5, false),
+                (2, false),
+                (3, false)
+            ])?,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_endgame_draw() -> Result<(), crate::Error> {
+        let rules = Rules {
+            repetition_draw: false,
+            king_moves_draw: None,
+            ..Rules::default()
+        };
+
+        // three kings against a lone king on the main road
+        let start = setup_endgame(&[(29, true), (1, false), (5, false), (2, false)], rules)?;
+        let mut game = Game::from_position(start, true);
+
+        for notation in ["29-25", "2-7", "25-29", "7-2"]
+            .repeat(8)
+            .into_iter()
+            .take(30)
+        {
+            assert_eq!(game.status(), Status::Ongoing);
+
+            let checkers_move = game.board().parse_move(notation, game.current_white())?;
+            game.play(&checkers_move)?;
+        }
+
+        assert_eq!(game.status(), Status::Draw(DrawReason::Endgame));
+
+        // leaving the main road doesn't restart the count, but the 4 pieces limit applies
+        let checkers_move = game.board().parse_move("25-21", true)?;
+        game.play(&checkers_move)?;
+        assert_eq!(game.endgame_moves(), Some((30, 31)));
+        assert_eq!(game.status(), Status::Ongoing);
+
+        // two kings against one
+        let start = setup_endgame(&[(29, true), (1, false), (3, false)], rules)?;
+        let mut game = Game::from_position(start, true);
+
+        for notation in ["29-25", "3-7", "25-29", "7-3"]
+            .repeat(3)
+            .into_iter()
+            .take(10)
+        {
+            assert_eq!(game.status(), Status::Ongoing);
+
+            let checkers_move = game.board().parse_move(notation, game.current_white())?;
+            game.play(&checkers_move)?;
+        }
+
+        assert_eq!(game.status(), Status::Draw(DrawReason::Endgame));
+
+        Ok(())
+    }
+}
@@ -10,7 +10,7 @@ mod board_builder;
 pub use board_builder::BoardBuilder;
 
 mod board;
-pub use board::Board;
+pub use board::{Board, MoveUndo, Pieces, Row, Rows};
 
 mod piece;
 pub use piece::Piece;
@@ -18,8 +18,39 @@ pub use piece::Piece;
 mod checkers_move;
 pub use checkers_move::CheckersMove;
 
+mod movegen;
+pub use movegen::{CompactMove, MoveList};
+
+mod perft;
+pub use perft::Divide;
+
+mod game;
+pub use game::{DrawReason, Game, Status};
+
+mod engine;
+pub use engine::{is_mate, Engine, Limits, SearchResult, MATE};
+
+mod eval;
+pub use eval::{Evaluator, HandcraftedEvaluator, MaterialEvaluator};
+
+mod tt;
+pub use tt::{Bound, TranspositionEntry, TranspositionTable};
+
+mod rules;
+pub use rules::{CapturePriority, CapturePromotion, Rules, Variant};
+
 pub mod position;
-pub use position::Position;
+pub use position::{BoardSize, Position};
+
+pub mod notation;
+
+pub mod fen;
+
+pub mod packed;
+
+pub mod pdn;
+
+mod zobrist;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -28,6 +59,18 @@ pub enum Error {
 
     #[error("")]
     Rules(#[from] RulesError),
+
+    #[error("")]
+    Notation(#[from] notation::Error),
+
+    #[error("")]
+    Fen(#[from] fen::Error),
+
+    #[error("")]
+    Packed(#[from] packed::Error),
+
+    #[error("")]
+    Pdn(#[from] pdn::Error),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -54,5 +97,8 @@ pub enum RulesError {
     },
 
     #[error("piece at {0} is not a king")]
-    NotKing(Position)
+    NotKing(Position),
+
+    #[error("move {0} is not legal")]
+    IllegalMove(CheckersMove),
 }
@@ -0,0 +1,741 @@
+use std::ops::Deref;
+
+use crate::{
+    Board, BoardSize, CapturePriority, CapturePromotion, CheckersMove, Piece, Position, RulesError,
+};
+
+/// Diagonal directions in the order moves are generated in.
+const DIRECTIONS: [(i8, i8); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+/// Compact, allocation-free representation of a move.
+///
+/// Unlike [`CheckersMove`] it does not store the moving piece, only the squares, so it
+/// has to be applied to (or expanded with) the [`Board`] it was generated from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CompactMove {
+    from: Position,
+    to: Position,
+    promotes: bool,
+    captures: u64,
+    /// Index among the capture sequences of the moving piece with the same destination
+    /// and captured squares, in the order they are generated.
+    route: u8,
+}
+
+impl CompactMove {
+    const EMPTY: Self = Self {
+        from: Position::new_unchecked(1, BoardSize::Eight),
+        to: Position::new_unchecked(1, BoardSize::Eight),
+        promotes: false,
+        captures: 0,
+        route: 0,
+    };
+
+    #[must_use]
+    pub fn from(self) -> Position {
+        self.from
+    }
+
+    #[must_use]
+    pub fn to(self) -> Position {
+        self.to
+    }
+
+    /// Returns if the moving piece gets promoted by this move.
+    #[must_use]
+    pub fn promotes(self) -> bool {
+        self.promotes
+    }
+
+    /// Bitmask of captured squares, bit `n - 1` corresponding to square `n`.
+    #[must_use]
+    pub fn captures_mask(self) -> u64 {
+        self.captures
+    }
+
+    #[must_use]
+    pub fn is_capture(self) -> bool {
+        self.captures != 0
+    }
+
+    /// Tells apart capture sequences capturing the same pieces and ending on the same
+    /// square: the index of this one among them, in the order they are generated.
+    #[must_use]
+    pub fn route(self) -> u8 {
+        self.route
+    }
+
+    /// Encodes `self` into a 64-bit integer, e.g. to key opening books and databases.
+    ///
+    /// Bits `0..6` hold the origin square, bits `6..12` the destination square, bit `12`
+    /// the promotion flag, bits `13..53` the captured squares mask (see
+    /// [`Self::captures_mask`]) without the first and last rows, where no piece can be
+    /// captured, and bits `53..61` the [`Self::route`]. The board size is not stored, see
+    /// [`Self::decode`].
+    #[must_use]
+    pub fn encode(self) -> u64 {
+        u64::from(u8::from(self.from))
+            | u64::from(u8::from(self.to)) << 6
+            | u64::from(self.promotes) << 12
+            | self.captures >> row_len(self.from.size()) << 13
+            | u64::from(self.route) << 53
+    }
+
+    /// Decodes a move of a board of `size` encoded by [`Self::encode`].
+    ///
+    /// # Errors
+    ///
+    /// - [`position::Error::OutOfBounds`][0] if a square of `code` is not on a board of
+    ///   `size`, or a captured square is on its first or last row.
+    ///
+    /// [0]: crate::position::Error::OutOfBounds
+    pub fn decode(code: u64, size: BoardSize) -> Result<Self, crate::position::Error> {
+        #[allow(clippy::cast_possible_truncation)]
+        let square = |shift: u32| Position::try_new((code >> shift & 0x3f) as u8, size);
+        let captures = code >> 13 & ((1 << 40) - 1);
+
+        if captures >> (size.squares() - 2 * row_len(size)) != 0 || code >> 61 != 0 {
+            return Err(crate::position::Error::OutOfBounds);
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(Self {
+            from: square(0)?,
+            to: square(6)?,
+            promotes: code >> 12 & 1 != 0,
+            captures: captures << row_len(size),
+            route: (code >> 53) as u8,
+        })
+    }
+
+    /// Converts `self` into a [`CheckersMove`] using the moving piece from `board`.
+    ///
+    /// A compact move does not store its capture path, so it is looked up among the
+    /// capture sequences of the moving piece by its [`Self::route`].
+    ///
+    /// # Errors
+    ///
+    /// - [`RulesError::Empty`] if there is no piece to move in `board`.
+    pub fn expand(self, board: &Board) -> Result<CheckersMove, RulesError> {
+        let old = board
+            .get_tile(self.from)
+            .ok_or(RulesError::Empty(self.from))?;
+
+        let mut expanded = None;
+
+        if self.is_capture() {
+            piece_captures(board, old, &mut |compact, path| {
+                if compact == self {
+                    expanded = Some(compact.with_path(old, path));
+                }
+            });
+        }
+
+        Ok(expanded.unwrap_or_else(|| self.with_path(old, &Path::EMPTY)))
+    }
+
+    /// Converts `self` into a [`CheckersMove`] moving `old` along `path`.
+    pub(crate) fn with_path(self, old: Piece, path: &Path) -> CheckersMove {
+        let new = Piece {
+            is_king: old.is_king || self.promotes,
+            is_white: old.is_white,
+            position: self.to,
+        };
+
+        if path.steps().is_empty() {
+            let captures = crate::board::squares(self.captures, self.to.size()).collect::<Vec<_>>();
+
+            return CheckersMove::arbitrary_with_path(old, new, vec![self.to], captures);
+        }
+
+        let (captures, landings) = path.steps().iter().copied().unzip();
+
+        CheckersMove::arbitrary_with_path(old, new, landings, captures)
+    }
+}
+
+/// Returns the number of playable squares in a row of a board of `size`.
+fn row_len(size: BoardSize) -> u8 {
+    size.rows() / 2
+}
+
+/// Fixed-capacity list of [`CompactMove`]s filled by [`Board::generate_moves`].
+///
+/// The list lives entirely on the stack, so it can be reused across calls without any
+/// heap allocation.
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [CompactMove; Self::CAPACITY],
+    len: usize,
+}
+
+impl MoveList {
+    /// Maximum number of moves the list can hold.
+    pub const CAPACITY: usize = 256;
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            moves: [CompactMove::EMPTY; Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Appends a move to the list.
+    ///
+    /// # Panics
+    ///
+    /// - the list already holds [`Self::CAPACITY`] moves.
+    pub fn push(&mut self, compact: CompactMove) {
+        assert!(self.len < Self::CAPACITY, "move list capacity exceeded");
+
+        self.moves[self.len] = compact;
+        self.len += 1;
+    }
+
+    #[must_use]
+    pub fn as_slice(&self) -> &[CompactMove] {
+        &self.moves[..self.len]
+    }
+
+    /// Keeps only the moves for which `f` returns `true`, preserving their order.
+    pub fn retain(&mut self, mut f: impl FnMut(CompactMove) -> bool) {
+        let mut kept = 0;
+
+        for i in 0..self.len {
+            if f(self.moves[i]) {
+                self.moves[kept] = self.moves[i];
+                kept += 1;
+            }
+        }
+
+        self.len = kept;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [CompactMove];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a CompactMove;
+
+    type IntoIter = std::slice::Iter<'a, CompactMove>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl std::fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+/// Maximum number of pieces captured in one move, all the pieces of one color on a 10x10
+/// board.
+const MAX_CAPTURES: usize = 20;
+
+/// Ordered capture steps of the move being generated, kept on the stack.
+#[derive(Clone, Copy)]
+pub(crate) struct Path {
+    /// `(captured, landing)` square pairs in the order they were jumped.
+    steps: [(Position, Position); MAX_CAPTURES],
+    len: usize,
+    captured: u64,
+}
+
+impl Path {
+    const EMPTY: Self = Self {
+        steps: [(
+            Position::new_unchecked(1, BoardSize::Eight),
+            Position::new_unchecked(1, BoardSize::Eight),
+        ); MAX_CAPTURES],
+        len: 0,
+        captured: 0,
+    };
+
+    fn push(&mut self, captured: Position, landing: Position) {
+        self.steps[self.len] = (captured, landing);
+        self.len += 1;
+        self.captured |= Board::bit(captured);
+    }
+
+    fn pop(&mut self) {
+        self.len -= 1;
+        self.captured &= !Board::bit(self.steps[self.len].0);
+    }
+
+    pub(crate) fn steps(&self) -> &[(Position, Position)] {
+        &self.steps[..self.len]
+    }
+}
+
+/// Generates captures of `piece`, calling `emit` for every complete capture sequence.
+///
+/// Returns if `piece` can capture at all.
+pub(crate) fn piece_captures(
+    board: &Board,
+    piece: Piece,
+    emit: &mut impl FnMut(CompactMove, &Path),
+) -> bool {
+    let mut path = Path::EMPTY;
+    let mut routes = None;
+
+    capture_sequences(
+        board,
+        piece,
+        piece.position,
+        piece.is_king,
+        &mut path,
+        &mut |mut compact, path| {
+            // most pieces can't capture, only set up the list once one does
+            compact.route = routes.get_or_insert_with(Routes::new).number(compact);
+            emit(compact, path);
+        },
+    )
+}
+
+/// Capture sequences of one piece generated so far, to number the ones capturing the
+/// same pieces and ending on the same square.
+struct Routes {
+    /// `(destination, captured squares)` of every sequence.
+    keys: [(Position, u64); MoveList::CAPACITY],
+    len: usize,
+}
+
+impl Routes {
+    fn new() -> Self {
+        Self {
+            keys: [(Position::new_unchecked(1, BoardSize::Eight), 0); MoveList::CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Records the sequence of `compact` and returns its route.
+    ///
+    /// # Panics
+    ///
+    /// - [`MoveList::CAPACITY`] sequences were recorded already.
+    fn number(&mut self, compact: CompactMove) -> u8 {
+        assert!(self.len < MoveList::CAPACITY, "move list capacity exceeded");
+
+        let key = (compact.to, compact.captures);
+        let route = self.keys[..self.len].iter().filter(|i| **i == key).count();
+
+        self.keys[self.len] = key;
+        self.len += 1;
+
+        u8::try_from(route).expect("route below the move list capacity")
+    }
+}
+
+/// Generates non-capturing moves of `piece`, calling `emit` for every move.
+pub(crate) fn piece_quiet_moves(
+    board: &Board,
+    piece: Piece,
+    emit: &mut impl FnMut(CompactMove, &Path),
+) {
+    let occupied = board.occupied();
+    let flying = piece.is_king && board.rules.flying_kings;
+
+    for direction in DIRECTIONS {
+        if !piece.is_king && is_backwards(piece, direction) {
+            continue;
+        }
+
+        let mut to = piece.position;
+        while let Ok(next) = to.increment(direction) {
+            to = next;
+
+            if occupied & Board::bit(to) != 0 {
+                break;
+            }
+
+            let compact = CompactMove {
+                from: piece.position,
+                to,
+                promotes: !piece.is_king && to.is_promoting(piece),
+                captures: 0,
+                route: 0,
+            };
+            emit(compact, &Path::EMPTY);
+
+            if !flying {
+                break;
+            }
+        }
+    }
+}
+
+/// Generates all moves of `piece`: its capture sequences, or its non-capturing moves
+/// if it can't capture.
+pub(crate) fn piece_moves(board: &Board, piece: Piece, emit: &mut impl FnMut(CompactMove, &Path)) {
+    if !piece_captures(board, piece, emit) {
+        piece_quiet_moves(board, piece, emit);
+    }
+}
+
+/// Generates all legal moves of `current_white`, calling `emit` for every move.
+///
+/// Captures are filtered by [`Rules::capture_priority`][0].
+///
+/// [0]: crate::Rules::capture_priority
+pub(crate) fn legal_moves(
+    board: &Board,
+    current_white: bool,
+    emit: &mut impl FnMut(CompactMove, &Path),
+) {
+    let mut best = [0; 4];
+
+    if board.rules.capture_priority != CapturePriority::Any {
+        for piece in board.pieces().filter(|i| i.is_white == current_white) {
+            piece_captures(board, piece, &mut |compact, path| {
+                best = best.max(capture_rank(board, piece, compact, path));
+            });
+        }
+    }
+
+    let mut can_capture = false;
+
+    for piece in board.pieces().filter(|i| i.is_white == current_white) {
+        can_capture |= piece_captures(board, piece, &mut |compact, path| {
+            if capture_rank(board, piece, compact, path) >= best {
+                emit(compact, path);
+            }
+        });
+    }
+
+    if !can_capture {
+        for piece in board.pieces().filter(|i| i.is_white == current_white) {
+            piece_quiet_moves(board, piece, emit);
+        }
+    }
+}
+
+/// Ranks a capture sequence of `piece` by [`Rules::capture_priority`][0], only the
+/// sequences of the highest rank may be chosen.
+///
+/// [0]: crate::Rules::capture_priority
+#[allow(clippy::cast_possible_truncation)]
+fn capture_rank(board: &Board, piece: Piece, compact: CompactMove, path: &Path) -> [u32; 4] {
+    let captures = compact.captures.count_ones();
+    let kings = (compact.captures & board.kings).count_ones();
+
+    match board.rules.capture_priority {
+        CapturePriority::Any => [0; 4],
+        CapturePriority::Majority => [captures, 0, 0, 0],
+        CapturePriority::MajorityKings => [captures, kings, 0, 0],
+        CapturePriority::Italian => {
+            // the earlier the first king is captured, the higher the rank
+            let first_king = path
+                .steps()
+                .iter()
+                .position(|(captured, _)| board.kings & Board::bit(*captured) != 0)
+                .map_or(0, |i| (MAX_CAPTURES - i) as u32);
+
+            [captures, u32::from(piece.is_king), kings, first_king]
+        }
+    }
+}
+
+/// Continues a capture sequence of `piece` standing at `pos`, having already made the
+/// captures in `path`.
+///
+/// Captured pieces stay on the board until the sequence ends (Turkish strike rule), so
+/// they block the way and can't be jumped over twice. If the sequence can continue from
+/// some of the squares behind a captured piece, it has to land on one of them. What
+/// happens to a man reaching the promoting row depends on
+/// [`Rules::capture_promotion`][0], but it is always promoted if the sequence ends there.
+///
+/// [0]: crate::Rules::capture_promotion
+fn capture_sequences(
+    board: &Board,
+    piece: Piece,
+    pos: Position,
+    is_king: bool,
+    path: &mut Path,
+    emit: &mut impl FnMut(CompactMove, &Path),
+) -> bool {
+    // the moving piece has left its square, but captured pieces are still there
+    let occupied = board.occupied() & !Board::bit(piece.position);
+    let mut enemies = if piece.is_white {
+        board.black
+    } else {
+        board.white
+    } & !path.captured;
+
+    if !is_king && !board.rules.men_capture_kings {
+        enemies &= !board.kings;
+    }
+
+    let flying = is_king && board.rules.flying_kings;
+    let mut found = false;
+
+    for direction in DIRECTIONS {
+        if !is_king && !board.rules.men_capture_backwards && is_backwards(piece, direction) {
+            continue;
+        }
+
+        let Ok(mut capture_pos) = pos.increment(direction) else {
+            continue;
+        };
+
+        if flying {
+            while occupied & Board::bit(capture_pos) == 0 {
+                let Ok(next) = capture_pos.increment(direction) else {
+                    break;
+                };
+                capture_pos = next;
+            }
+        }
+
+        if enemies & Board::bit(capture_pos) == 0 {
+            continue;
+        }
+
+        let mut continues = false;
+
+        for new_pos in landings(capture_pos, direction, occupied, flying) {
+            found = true;
+
+            let promotion = !is_king && new_pos.is_promoting(piece);
+            let rule = board.rules.capture_promotion;
+            let ends = promotion && rule == CapturePromotion::End;
+            let king = is_king || promotion && rule == CapturePromotion::Continue;
+
+            path.push(capture_pos, new_pos);
+            continues |= !ends && capture_sequences(board, piece, new_pos, king, path, emit);
+            path.pop();
+        }
+
+        // the sequence may only end here if it can't continue from any landing square
+        if continues {
+            continue;
+        }
+
+        for new_pos in landings(capture_pos, direction, occupied, flying) {
+            path.push(capture_pos, new_pos);
+
+            let compact = CompactMove {
+                from: piece.position,
+                to: new_pos,
+                promotes: !piece.is_king && (is_king || new_pos.is_promoting(piece)),
+                captures: path.captured,
+                route: 0,
+            };
+            emit(compact, path);
+
+            path.pop();
+        }
+    }
+
+    found
+}
+
+/// Returns the empty squares behind `captured` in `direction` a piece may land on, only
+/// the first one unless `flying`.
+fn landings(
+    captured: Position,
+    direction: (i8, i8),
+    occupied: u64,
+    flying: bool,
+) -> impl Iterator<Item = Position> {
+    std::iter::successors(captured.increment(direction).ok(), move |pos| {
+        pos.increment(direction).ok()
+    })
+    .take_while(move |pos| occupied & Board::bit(*pos) == 0)
+    .take(if flying { usize::MAX } else { 1 })
+}
+
+/// Returns if `direction` points away from the promoting row of `piece`.
+fn is_backwards(piece: Piece, direction: (i8, i8)) -> bool {
+    (direction.1 > 0) == piece.is_white
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use crate::BoardBuilder;
+
+    fn setup_board() -> Board {
+        BoardBuilder::try_from_template([
+            [0, 0, 0, 2, 0, 0, 0, 0],
+            [0, 0, 2, 0, 0, 0, 2, 0],
+            [0, 3, 0, 0, 0, 2, 0, 0],
+            [0, 0, 1, 0, 2, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0, 0, 4],
+            [1, 0, 0, 0, 1, 0, 1, 0],
+            [0, 2, 0, 0, 0, 0, 0, 1],
+            [0, 0, 4, 0, 0, 0, 3, 0],
+        ])
+        .unwrap()
+        .build()
+    }
+
+    #[test]
+    fn test_generate_moves() {
+        let board = setup_board();
+        let mut list = MoveList::new();
+
+        for current_white in [true, false] {
+            board.generate_moves(current_white, &mut list);
+
+            let expanded = list
+                .iter()
+                .map(|i| i.expand(&board).unwrap())
+                .collect::<Vec<_>>();
+
+            assert_eq!(expanded, board.legal_moves(current_white));
+        }
+    }
+
+    #[test]
+    fn test_apply_compact_move() {
+        let board = setup_board();
+        let mut list = MoveList::new();
+
+        for current_white in [true, false] {
+            board.generate_moves(current_white, &mut list);
+
+            for compact in &list {
+                let mut applied = board.clone();
+                let undo = applied.apply_compact_move(*compact);
+
+                assert_eq!(
+                    applied,
+                    board
+                        .clone()
+                        .applied_move(&compact.expand(&board).unwrap())
+                        .unwrap()
+                );
+
+                applied.unapply_compact_move(*compact, undo);
+                assert_eq!(applied, board);
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_kings() -> Result<(), crate::Error> {
+        let rules = crate::Rules {
+            flying_kings: false,
+            ..crate::Rules::default()
+        };
+
+        // King on the long diagonal with a black man to capture three squares away
+        let board = BoardBuilder::empty_with_rules(rules)
+            .try_insert(Piece::new(true, true, 29.try_into()?))?
+            .try_insert(Piece::new(false, false, 18.try_into()?))?
+            .build();
+        let moves = board.legal_moves(true);
+
+        assert_eq!(moves.len(), 1);
+        assert!(moves[0].captures().is_empty());
+        assert_eq!(u8::from(moves[0].new_piece().position), 25);
+
+        let flying = board.try_with_rules(crate::Rules::default())?;
+        assert_eq!(flying.legal_moves(true).len(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_retain() {
+        let board = BoardBuilder::default().build();
+        let mut list = MoveList::new();
+
+        board.generate_moves(true, &mut list);
+        list.retain(|i| u8::from(i.from()) == 21);
+
+        assert_eq!(list.len(), 1);
+        assert_eq!(u8::from(list[0].to()), 17);
+    }
+
+    #[test]
+    fn test_encode() -> Result<(), crate::Error> {
+        let board = setup_board();
+        let mut list = MoveList::new();
+
+        for current_white in [true, false] {
+            board.generate_moves(current_white, &mut list);
+
+            for compact in &list {
+                let decoded = CompactMove::decode(compact.encode(), BoardSize::Eight)?;
+                assert_eq!(decoded, *compact);
+            }
+
+            for m in board.legal_moves(current_white) {
+                assert_eq!(board.decode_move(board.encode_move(&m)?)?, m);
+            }
+        }
+
+        let (board, _) = crate::Board::from_fen_with_rules(
+            "W:WK46:B41,32",
+            crate::Variant::International.rules(),
+        )?;
+        let capture = &board.legal_moves(true)[0];
+
+        assert_eq!(board.decode_move(board.encode_move(capture)?)?, *capture);
+
+        CompactMove::decode(0, BoardSize::Eight).expect_err("decoded square 0");
+        CompactMove::decode(1 + (2 << 6) + (1 << 37), BoardSize::Eight)
+            .expect_err("decoded out of bounds capture");
+        CompactMove::decode(1 + (2 << 6) + (1 << 61), BoardSize::Eight)
+            .expect_err("decoded out of bounds route");
+
+        // 21-13 is not a legal move of the initial position
+        let board = BoardBuilder::default().build();
+        board
+            .decode_move(21 + (13 << 6))
+            .expect_err("decoded illegal move");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_routes() -> Result<(), crate::Error> {
+        // flying kings capturing the same pieces along several routes
+        let positions = [
+            ("W:WK22:B18,19,26,27", crate::Variant::Russian),
+            ("W:WK4:B11,18,31,39", crate::Variant::International),
+        ];
+
+        for (fen, variant) in positions {
+            let (board, current_white) = crate::Board::from_fen_with_rules(fen, variant.rules())?;
+            let mut list = MoveList::new();
+            board.generate_moves(current_white, &mut list);
+
+            assert!(list.iter().any(|i| i.route() > 0), "{fen}");
+
+            for m in board.legal_moves(current_white) {
+                let code = board.encode_move(&m)?;
+                assert_eq!(board.decode_move(code)?, m, "{fen}");
+
+                let compact = CompactMove::decode(code, variant.rules().board_size)?;
+                assert_eq!(compact.expand(&board)?, m, "{fen}");
+            }
+        }
+
+        Ok(())
+    }
+}
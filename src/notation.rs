@@ -0,0 +1,460 @@
+//! Move notation formatting and parsing.
+
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use crate::{Board, BoardSize, CheckersMove, Position};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid move notation {0:?}")]
+    Syntax(String),
+
+    #[error("invalid square {square:?}: {source}")]
+    Square {
+        square: String,
+        source: crate::position::Error,
+    },
+
+    #[error(
+        "{notation:?} is not a legal move, legal moves are: {}",
+        legal.iter().join(", ")
+    )]
+    Illegal {
+        notation: String,
+        legal: Vec<CheckersMove>,
+    },
+
+    #[error(
+        "{notation:?} is ambiguous, candidates are: {}",
+        candidates.iter().join(", ")
+    )]
+    Ambiguous {
+        notation: String,
+        candidates: Vec<CheckersMove>,
+    },
+}
+
+/// How squares are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// Square numbers, `22-18` and `22x15x8`.
+    #[default]
+    Numeric,
+
+    /// Coordinates with files `a..=h` from the left and ranks `1..=8` from the bottom,
+    /// `c3-d4` and `c3:e5:g7`.
+    Algebraic,
+}
+
+/// How captures with several jumps are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CaptureForm {
+    /// Only the starting and final squares, `22x8`.
+    Short,
+
+    /// The starting square followed by every landing square, `22x15x8`.
+    #[default]
+    Long,
+}
+
+/// Move notation formatter.
+///
+/// Moves are always written from the squares the piece lands on in the order it lands
+/// on them (see [`CheckersMove::path`]), so the output is stable between runs.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # use checkers_lib::*;
+/// # use checkers_lib::notation::*;
+/// #
+/// let board = BoardBuilder::empty()
+///     .try_insert(Piece::new(false, true, 22.try_into()?))?
+///     .try_insert(Piece::new(false, false, 18.try_into()?))?
+///     .try_insert(Piece::new(false, false, 11.try_into()?))?
+///     .build();
+/// let capture = &board.legal_moves(true)[0];
+///
+/// assert_eq!(Notation::default().format(capture), "22x15x8");
+/// assert_eq!(Notation::new(Style::Numeric, CaptureForm::Short).format(capture), "22x8");
+/// assert_eq!(Notation::new(Style::Algebraic, CaptureForm::Long).format(capture), "c3:e5:g7");
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Notation {
+    pub style: Style,
+    pub capture_form: CaptureForm,
+}
+
+impl Notation {
+    #[must_use]
+    pub fn new(style: Style, capture_form: CaptureForm) -> Self {
+        Self {
+            style,
+            capture_form,
+        }
+    }
+
+    /// Returns the notation of `checkers_move`.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn format(self, checkers_move: &CheckersMove) -> String {
+        let mut buf = String::new();
+
+        // writing into a `String` never fails
+        self.write(&mut buf, checkers_move).unwrap();
+
+        buf
+    }
+
+    /// Writes the notation of `checkers_move` into `f`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing into `f` fails.
+    pub fn write(self, f: &mut impl Write, checkers_move: &CheckersMove) -> std::fmt::Result {
+        self.write_square(f, checkers_move.old_piece().position)?;
+
+        if checkers_move.captures().is_empty() {
+            f.write_char('-')?;
+            return self.write_square(f, checkers_move.new_piece().position);
+        }
+
+        let separator = match self.style {
+            Style::Numeric => 'x',
+            Style::Algebraic => ':',
+        };
+
+        let landings = match self.capture_form {
+            CaptureForm::Short => std::slice::from_ref(&checkers_move.new.position),
+            CaptureForm::Long => checkers_move.path(),
+        };
+
+        for landing in landings {
+            f.write_char(separator)?;
+            self.write_square(f, *landing)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_square(self, f: &mut impl Write, pos: Position) -> std::fmt::Result {
+        match self.style {
+            Style::Numeric => write!(f, "{pos}"),
+            Style::Algebraic => write!(f, "{}", algebraic(pos)),
+        }
+    }
+}
+
+/// Returns the algebraic name of `pos`, e.g. `c3` for square 22 of an 8x8 board.
+#[must_use]
+pub fn algebraic(pos: Position) -> String {
+    let (x, y) = pos.coords();
+
+    // x and y are both in 0..10
+    #[allow(clippy::cast_possible_truncation)]
+    let (file, rank) = (
+        char::from(b'a' + x as u8),
+        usize::from(pos.size().rows()) - y,
+    );
+
+    format!("{file}{rank}")
+}
+
+/// Parses a square of an 8x8 board in either numeric (`22`) or algebraic (`c3`) style.
+///
+/// # Errors
+///
+/// See [`parse_square_with_size`].
+pub fn parse_square(square: &str) -> Result<Position, Error> {
+    parse_square_with_size(square, BoardSize::Eight)
+}
+
+/// Parses a square of a board of `size` in either numeric (`22`) or algebraic (`c3`)
+/// style.
+///
+/// # Errors
+///
+/// - [`Error::Syntax`] if `square` is neither a number nor a file and rank,
+/// - [`Error::Square`] if `square` is out of bounds or names a white square.
+pub fn parse_square_with_size(square: &str, size: BoardSize) -> Result<Position, Error> {
+    let invalid = |source| Error::Square {
+        square: square.to_owned(),
+        source,
+    };
+
+    if let Ok(num) = square.parse::<u8>() {
+        return Position::try_new(num, size).map_err(invalid);
+    }
+
+    let mut chars = square.chars();
+    let (Some(file @ 'a'..='z'), Ok(rank)) = (chars.next(), chars.as_str().parse::<usize>()) else {
+        return Err(Error::Syntax(square.to_owned()));
+    };
+
+    let x = file as usize - 'a' as usize;
+    let y = usize::from(size.rows())
+        .checked_sub(rank)
+        .filter(|_| rank > 0)
+        .ok_or(invalid(crate::position::Error::OutOfBounds))?;
+
+    Position::try_from_coords((x, y), size).map_err(invalid)
+}
+
+/// Splits `notation` into its squares of a board of `size`, returning them with whether
+/// `notation` is a capture.
+///
+/// Simple moves are separated by `-`, captures by `x` or `:`.
+fn parse_squares(notation: &str, size: BoardSize) -> Result<(Vec<Position>, bool), Error> {
+    let notation = notation.trim();
+    let is_capture = notation.contains(['x', 'X', ':']);
+
+    if is_capture && notation.contains('-') {
+        return Err(Error::Syntax(notation.to_owned()));
+    }
+
+    let squares = notation
+        .split(['-', 'x', 'X', ':'])
+        .map(|square| parse_square_with_size(square, size))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if squares.len() < 2 {
+        return Err(Error::Syntax(notation.to_owned()));
+    }
+
+    Ok((squares, is_capture))
+}
+
+/// Returns if `checkers_move` is a capture if and only if `is_capture`, starts on the
+/// first of `squares`, ends on the last one and lands on all the others in order.
+fn matches(checkers_move: &CheckersMove, squares: &[Position], is_capture: bool) -> bool {
+    let (Some(first), Some(last)) = (squares.first(), squares.last()) else {
+        return false;
+    };
+
+    if checkers_move.captures().is_empty() == is_capture {
+        return false;
+    }
+
+    if checkers_move.old_piece().position != *first || checkers_move.new_piece().position != *last {
+        return false;
+    }
+
+    let mut path = checkers_move.path().iter();
+    squares[1..squares.len() - 1]
+        .iter()
+        .all(|square| path.any(|landing| landing == square))
+}
+
+impl Board {
+    /// Finds the legal move of `current_white` written as `notation`.
+    ///
+    /// Both numeric (`11-15`, `22x15x8`) and algebraic (`c3-d4`, `c3:e5:g7`) squares are
+    /// accepted. Simple moves are written with `-` and captures with `x` or `:`. Captures
+    /// may list only the starting and final squares, or any of the landing squares in
+    /// between.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Syntax`] or [`Error::Square`] if `notation` can't be parsed,
+    /// - [`Error::Illegal`] with all legal moves if no legal move matches `notation`,
+    /// - [`Error::Ambiguous`] with the matching moves if more than one does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use checkers_lib::*;
+    /// #
+    /// let board = BoardBuilder::default().build();
+    ///
+    /// let a = board.parse_move("22-18", true)?;
+    /// let b = board.parse_move("c3-d4", true)?;
+    ///
+    /// assert_eq!(a, b);
+    /// assert!(board.parse_move("22-17", false).is_err());
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_move(&self, notation: &str, current_white: bool) -> Result<CheckersMove, Error> {
+        let (squares, is_capture) = parse_squares(notation, self.rules.board_size)?;
+        let legal = self.legal_moves(current_white);
+
+        let mut candidates = legal
+            .iter()
+            .filter(|i| matches(i, &squares, is_capture))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match candidates.len() {
+            0 => Err(Error::Illegal {
+                notation: notation.to_owned(),
+                legal,
+            }),
+            1 => Ok(candidates.remove(0)),
+            _ => Err(Error::Ambiguous {
+                notation: notation.to_owned(),
+                candidates,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use crate::{BoardBuilder, Piece};
+
+    #[test]
+    fn test_algebraic() -> Result<(), crate::Error> {
+        assert_eq!(algebraic(1.try_into()?), "b8");
+        assert_eq!(algebraic(22.try_into()?), "c3");
+        assert_eq!(algebraic(29.try_into()?), "a1");
+        assert_eq!(algebraic(32.try_into()?), "g1");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format() -> Result<(), crate::Error> {
+        let board = BoardBuilder::empty()
+            .try_insert(Piece::new(false, true, 22.try_into()?))?
+            .try_insert(Piece::new(false, false, 18.try_into()?))?
+            .try_insert(Piece::new(false, false, 11.try_into()?))?
+            .build();
+        let capture = &board.legal_moves(true)[0];
+        let quiet = &BoardBuilder::empty()
+            .try_insert(Piece::new(false, true, 30.try_into()?))?
+            .build()
+            .legal_moves(true)[0];
+
+        let formats = [
+            (Style::Numeric, CaptureForm::Long, "22x15x8", "30-25"),
+            (Style::Numeric, CaptureForm::Short, "22x8", "30-25"),
+            (Style::Algebraic, CaptureForm::Long, "c3:e5:g7", "c1-b2"),
+            (Style::Algebraic, CaptureForm::Short, "c3:g7", "c1-b2"),
+        ];
+
+        for (style, capture_form, capture_result, quiet_result) in formats {
+            let notation = Notation::new(style, capture_form);
+
+            assert_eq!(notation.format(capture), capture_result);
+            assert_eq!(notation.format(quiet), quiet_result);
+        }
+
+        assert_eq!(format!("{capture}"), "22x15x8");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_square() -> Result<(), crate::Error> {
+        assert_eq!(parse_square("22")?, 22.try_into()?);
+        assert_eq!(parse_square("c3")?, 22.try_into()?);
+        assert_eq!(parse_square("h8")?, 4.try_into()?);
+
+        parse_square("33").expect_err("parsed out of bounds square");
+        parse_square("a8").expect_err("parsed white square");
+        parse_square("c9").expect_err("parsed out of bounds square");
+        parse_square("c0").expect_err("parsed out of bounds square");
+        parse_square("j3").expect_err("parsed out of bounds square");
+        parse_square("3c").expect_err("parsed invalid square");
+
+        let square = parse_square_with_size("a1", BoardSize::Ten)?;
+        assert_eq!(square, Position::try_new(46, BoardSize::Ten)?);
+        assert_eq!(algebraic(square), "a1");
+        assert_eq!(
+            parse_square_with_size("50", BoardSize::Ten)?,
+            parse_square_with_size("i1", BoardSize::Ten)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_move() -> Result<(), crate::Error> {
+        let board = BoardBuilder::default().build();
+
+        let quiet = board.parse_move("11-15", false)?;
+        assert_eq!(format!("{quiet}"), "11-15");
+        assert_eq!(
+            board.parse_move("c3-d4", true)?,
+            board.parse_move("22-18", true)?
+        );
+
+        assert!(matches!(
+            board.parse_move("22-17", false),
+            Err(Error::Illegal { legal, .. }) if legal.len() == 7
+        ));
+        assert!(matches!(
+            board.parse_move("22", true),
+            Err(Error::Syntax(_))
+        ));
+        assert!(matches!(
+            board.parse_move("22-", true),
+            Err(Error::Syntax(_))
+        ));
+
+        let board = BoardBuilder::empty()
+            .try_insert(Piece::new(false, true, 22.try_into()?))?
+            .try_insert(Piece::new(false, false, 18.try_into()?))?
+            .try_insert(Piece::new(false, false, 11.try_into()?))?
+            .build();
+
+        let capture = board.parse_move("22x8", true)?;
+        assert_eq!(board.parse_move("22x15x8", true)?, capture);
+        assert_eq!(board.parse_move("c3:e5:g7", true)?, capture);
+        board
+            .parse_move("22x16x8", true)
+            .expect_err("parsed capture through wrong square");
+        board
+            .parse_move("22-8", true)
+            .expect_err("parsed capture written as a simple move");
+        board
+            .parse_move("22-15x8", true)
+            .expect_err("parsed mixed separators");
+
+        let board = BoardBuilder::default().build();
+        board
+            .parse_move("22x18", true)
+            .expect_err("parsed simple move written as a capture");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ambiguous() -> Result<(), crate::Error> {
+        // Man capturing a loop of four pieces in either direction
+        let board = BoardBuilder::try_from_template([
+            [0, 0, 0, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 0, 0, 0],
+            [0, 0, 0, 2, 0, 2, 0, 0],
+            [0, 0, 1, 0, 0, 0, 0, 0],
+            [0, 0, 0, 2, 0, 2, 0, 0],
+            [0, 0, 0, 0, 0, 0, 0, 0],
+        ])?
+        .build();
+
+        let Err(Error::Ambiguous { candidates, .. }) = board.parse_move("c3xc3", true) else {
+            panic!("parsed ambiguous capture");
+        };
+        assert_eq!(candidates.len(), 2);
+
+        let capture = board.parse_move("c3:e5:g3:e1:c3", true)?;
+        assert_eq!(capture.captures_in_order().len(), 4);
+
+        Ok(())
+    }
+}
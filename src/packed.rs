@@ -0,0 +1,296 @@
+//! Compact fixed-size binary encoding of a board and the side to move.
+//!
+//! A packed board holds the side to move in its first bit, followed by the masks of the
+//! white pieces, the black pieces and the kings, each using one bit per square of the
+//! board (see [`Board::white_mask`]). Bits are stored little-endian and the last byte is
+//! padded with zeros, so a packed board takes [`len`] bytes: 13 on an 8x8 board and 19 on
+//! a 10x10 one.
+//!
+//! The rules are not stored, the decoder is given them like [`Board::from_fen_with_rules`].
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # use checkers_lib::*;
+//! #
+//! let board = BoardBuilder::default().build();
+//! let bytes = board.to_packed(true);
+//!
+//! assert_eq!(bytes.len(), packed::len(BoardSize::Eight));
+//! assert_eq!(Board::from_packed(&bytes, Rules::default())?, (board, true));
+//! #
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{board, Board, BoardBuilder, BoardSize, Rules};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("packed board has {found} bytes, expected {expected}")]
+    Length { expected: usize, found: usize },
+
+    #[error("packed board has a square with both a white and a black piece")]
+    Overlap,
+
+    #[error("packed board has a king on an empty square")]
+    EmptyKing,
+
+    #[error("packed board has nonzero padding bits")]
+    Padding,
+}
+
+/// Returns the number of bytes of a packed board of `size`.
+#[must_use]
+pub const fn len(size: BoardSize) -> usize {
+    (3 * size.squares() as usize + 1).div_ceil(8)
+}
+
+/// Number of bytes of a packed board of the largest size.
+pub const MAX_LEN: usize = len(BoardSize::Ten);
+
+/// Packed board returned by [`Board::to_packed`], stored inline without allocating.
+///
+/// It dereferences to its [`len`] bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PackedBoard {
+    bytes: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl std::ops::Deref for PackedBoard {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes[..self.len]
+    }
+}
+
+impl AsRef<[u8]> for PackedBoard {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Board {
+    /// Returns `self` with `current_white` to move packed into [`packed::len`][0] bytes,
+    /// see [`packed`][1].
+    ///
+    /// [0]: crate::packed::len
+    /// [1]: crate::packed
+    #[must_use]
+    pub fn to_packed(&self, current_white: bool) -> PackedBoard {
+        let squares = u32::from(self.rules.board_size.squares());
+        let mut writer = Writer {
+            bytes: PackedBoard {
+                bytes: [0; MAX_LEN],
+                len: 0,
+            },
+            acc: 0,
+            bits: 0,
+        };
+
+        writer.push(u64::from(current_white), 1);
+        writer.push(self.white, squares);
+        writer.push(self.black, squares);
+        writer.push(self.kings, squares);
+        writer.finish()
+    }
+
+    /// Unpacks a board played with `rules` and the side to move from `bytes`, see
+    /// [`packed`][0].
+    ///
+    /// Pieces are inserted with [`BoardBuilder::try_insert`], so the same validation
+    /// applies as when building a board by hand.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Packed`][1] if `bytes` has the wrong length, or its masks are
+    ///   inconsistent,
+    /// - any error of [`BoardBuilder::try_insert`].
+    ///
+    /// [0]: crate::packed
+    /// [1]: crate::Error::Packed
+    pub fn from_packed(bytes: &[u8], rules: Rules) -> Result<(Board, bool), crate::Error> {
+        let size = rules.board_size;
+
+        if bytes.len() != len(size) {
+            Err(Error::Length {
+                expected: len(size),
+                found: bytes.len(),
+            })?;
+        }
+
+        let squares = u32::from(size.squares());
+        let mut reader = Reader {
+            bytes: bytes.iter(),
+            acc: 0,
+            bits: 0,
+        };
+
+        let current_white = reader.pop(1) != 0;
+        let white = reader.pop(squares);
+        let black = reader.pop(squares);
+        let kings = reader.pop(squares);
+
+        if reader.acc != 0 {
+            Err(Error::Padding)?;
+        }
+
+        if white & black != 0 {
+            Err(Error::Overlap)?;
+        }
+
+        if kings & !(white | black) != 0 {
+            Err(Error::EmptyKing)?;
+        }
+
+        let mut builder = BoardBuilder::empty_with_rules(rules);
+
+        for position in board::squares(white | black, size) {
+            let bit = Board::bit(position);
+
+            builder = builder.try_insert(crate::Piece::new(
+                kings & bit != 0,
+                white & bit != 0,
+                position,
+            ))?;
+        }
+
+        Ok((builder.build(), current_white))
+    }
+}
+
+/// Writes fields of bits into bytes, least significant bit first.
+struct Writer {
+    bytes: PackedBoard,
+    acc: u128,
+    bits: u32,
+}
+
+impl Writer {
+    /// Writes the lowest `bits` bits of `value`.
+    fn push(&mut self, value: u64, bits: u32) {
+        self.acc |= u128::from(value) << self.bits;
+        self.bits += bits;
+
+        while self.bits >= 8 {
+            #[allow(clippy::cast_possible_truncation)]
+            self.write_byte(self.acc as u8);
+            self.acc >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    /// Returns the written bytes, padding the last one with zeros.
+    #[allow(clippy::cast_possible_truncation)]
+    fn finish(mut self) -> PackedBoard {
+        if self.bits > 0 {
+            self.write_byte(self.acc as u8);
+        }
+
+        self.bytes
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        self.bytes.bytes[self.bytes.len] = byte;
+        self.bytes.len += 1;
+    }
+}
+
+/// Reads fields of bits written by [`Writer`].
+struct Reader<'a> {
+    bytes: std::slice::Iter<'a, u8>,
+    acc: u128,
+    bits: u32,
+}
+
+impl Reader<'_> {
+    /// Reads a field of `bits` bits, at most 64.
+    fn pop(&mut self, bits: u32) -> u64 {
+        while self.bits < bits {
+            let byte = self.bytes.next().copied().unwrap_or_default();
+            self.acc |= u128::from(byte) << self.bits;
+            self.bits += 8;
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let value = (self.acc & ((1 << bits) - 1)) as u64;
+        self.acc >>= bits;
+        self.bits -= bits;
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use crate::Variant;
+
+    #[test]
+    fn test_round_trip() -> Result<(), crate::Error> {
+        for variant in Variant::ALL {
+            let rules = variant.rules();
+            let board = BoardBuilder::initial(rules).build();
+
+            for current_white in [true, false] {
+                let bytes = board.to_packed(current_white);
+
+                assert_eq!(bytes.len(), len(rules.board_size));
+                assert!(bytes.len() <= MAX_LEN);
+                assert_eq!(
+                    Board::from_packed(&bytes, rules)?,
+                    (board.clone(), current_white)
+                );
+            }
+        }
+
+        let (board, current_white) = Board::from_fen("B:WK1,18,K32:B3,K14,28")?;
+        let bytes = board.to_packed(current_white);
+
+        assert_eq!(
+            Board::from_packed(&bytes, Rules::default())?,
+            (board, current_white)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() -> Result<(), crate::Error> {
+        let rules = Rules::default();
+        let bytes = BoardBuilder::initial(rules).build().to_packed(true);
+
+        Board::from_packed(&bytes[1..], rules).expect_err("unpacked short board");
+        Board::from_packed(&bytes, Variant::International.rules())
+            .expect_err("unpacked board of a different size");
+
+        let mut padded = bytes.to_vec();
+        padded[12] |= 0x80;
+        Board::from_packed(&padded, rules).expect_err("unpacked nonzero padding");
+
+        // white and black piece on square 1
+        let mut overlap = bytes.to_vec();
+        overlap[0] |= 0x02;
+        Board::from_packed(&overlap, rules).expect_err("unpacked overlapping pieces");
+
+        // king on empty square 13
+        let mut king = bytes.to_vec();
+        king[9] |= 0x20;
+        Board::from_packed(&king, rules).expect_err("unpacked king on empty square");
+
+        // white man on promoting square 1
+        let (board, _) = Board::from_fen("W:WK1:B")?;
+        let mut man = board.to_packed(true).to_vec();
+        man[8] &= !0x02;
+        Board::from_packed(&man, rules).expect_err("unpacked man on promoting square");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,642 @@
+//! Portable Draughts Notation game files.
+//!
+//! [`parse`] reads any number of games, replaying every move through
+//! [`Board::parse_move`] so only legal games are accepted, and [`Record`]'s `Display`
+//! impl writes a game back. Games are played with the [`Rules`] of their `GameType`
+//! tag, Russian draughts if it is missing.
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # use checkers_lib::*;
+//! #
+//! let text = r#"
+//! [Event "Club championship"]
+//! [Result "*"]
+//!
+//! 1. 22-18 11-15 2. 18x11 {forced} 8x15 $1 *
+//! "#;
+//!
+//! let games = pdn::parse(text)?;
+//! let game = &games[0];
+//!
+//! assert_eq!(game.tag("Event"), Some("Club championship"));
+//! assert_eq!(game.plies.len(), 4);
+//! assert_eq!(game.plies[2].comment.as_deref(), Some("forced"));
+//! assert_eq!(game.plies[3].nags, [1]);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::{Board, BoardBuilder, CheckersMove, Rules, Variant};
+
+/// Maximum length of a move text line when writing.
+const LINE_WIDTH: usize = 80;
+
+/// Result tokens that end the move text of a game.
+const RESULTS: [&str; 8] = ["1-0", "0-1", "2-0", "0-2", "1-1", "0-0", "1/2-1/2", "*"];
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid PDN: {0}")]
+    Syntax(String),
+
+    #[error("invalid move at ply {ply}: {source}")]
+    Move {
+        ply: usize,
+        source: crate::notation::Error,
+    },
+}
+
+/// A single move of a [`Record`] with its annotations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ply {
+    pub checkers_move: CheckersMove,
+    pub comment: Option<String>,
+    /// Numeric annotation glyphs, `$1` being `1`.
+    pub nags: Vec<u16>,
+}
+
+impl Ply {
+    #[must_use]
+    pub fn new(checkers_move: CheckersMove) -> Self {
+        Self {
+            checkers_move,
+            comment: None,
+            nags: Vec::new(),
+        }
+    }
+}
+
+/// A single game of a PDN file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// Header tags in file order, e.g. `("Event", "Club championship")`.
+    pub tags: Vec<(String, String)>,
+    /// Comment before the first move.
+    pub comment: Option<String>,
+    /// Starting position, taken from the `FEN` tag if present and played with the rules
+    /// of the `GameType` tag.
+    pub start: Board,
+    /// Side to move in [`Self::start`].
+    pub start_white: bool,
+    pub plies: Vec<Ply>,
+    /// Result token ending the move text, e.g. `2-0` or `*` for an unfinished game.
+    pub result: String,
+}
+
+impl Record {
+    /// Creates a game without tags or moves starting from `start`.
+    #[must_use]
+    pub fn new(start: Board, start_white: bool) -> Self {
+        Self {
+            tags: Vec::new(),
+            comment: None,
+            start,
+            start_white,
+            plies: Vec::new(),
+            result: "*".to_owned(),
+        }
+    }
+
+    /// Returns the value of the first tag called `name`.
+    #[must_use]
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the value of the tag called `name`, adding it if missing.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => value.clone_into(old),
+            None => self.tags.push((name.to_owned(), value.to_owned())),
+        }
+    }
+
+    /// Returns the position after the last move and the side to move in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a move can't be applied, which never happens for records
+    /// returned by [`parse`].
+    pub fn final_position(&self) -> Result<(Board, bool), crate::Error> {
+        let mut board = self.start.clone();
+        let mut current_white = self.start_white;
+
+        for ply in &self.plies {
+            board.apply_move(&ply.checkers_move)?;
+            current_white = !current_white;
+        }
+
+        Ok((board, current_white))
+    }
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        Self::new(BoardBuilder::default().build(), true)
+    }
+}
+
+impl FromStr for Record {
+    type Err = crate::Error;
+
+    /// Parses a single game, see [`parse`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut games = parse(s)?;
+
+        if games.len() != 1 {
+            Err(Error::Syntax(format!(
+                "expected 1 game, found {}",
+                games.len()
+            )))?;
+        }
+
+        Ok(games.remove(0))
+    }
+}
+
+/// Writes the game as PDN: tags, a blank line and the move text with move numbers,
+/// comments, NAGs and the result.
+///
+/// Missing `GameType` (for the rules of any [`Variant`] but Russian draughts) and `FEN`
+/// tags are added, so the game reads back with the same rules and starting position.
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (name, value) in &self.tags {
+            writeln!(
+                f,
+                "[{name} \"{}\"]",
+                value.replace('\\', "\\\\").replace('"', "\\\"")
+            )?;
+        }
+
+        let rules = self.start.rules();
+        let initial = self.start == BoardBuilder::initial(rules).build()
+            && self.start_white == rules.white_moves_first;
+
+        let mut header = !self.tags.is_empty();
+
+        // games without a `GameType` tag are read as Russian draughts
+        if self.tag("GameType").is_none() && rules != Rules::default() {
+            if let Some(variant) = Variant::ALL.into_iter().find(|i| i.rules() == rules) {
+                writeln!(f, "[GameType \"{}\"]", variant.game_type())?;
+                header = true;
+            }
+        }
+
+        if self.tag("FEN").is_none() && !initial {
+            writeln!(f, "[FEN \"{}\"]", self.start.to_fen(self.start_white))?;
+            header = true;
+        }
+
+        if header {
+            writeln!(f)?;
+        }
+
+        let mut tokens = Vec::new();
+
+        if let Some(comment) = &self.comment {
+            tokens.push(format!("{{{comment}}}"));
+        }
+
+        // moves are numbered from the side making the first move of a game
+        let first_white = self.start.rules().white_moves_first;
+        let mut current_white = self.start_white;
+        let mut number = 1;
+
+        for (i, ply) in self.plies.iter().enumerate() {
+            if current_white == first_white {
+                tokens.push(format!("{number}."));
+            } else if i == 0 {
+                tokens.push(format!("{number}..."));
+            }
+
+            tokens.push(format!("{}", ply.checkers_move));
+            tokens.extend(ply.nags.iter().map(|nag| format!("${nag}")));
+
+            if let Some(comment) = &ply.comment {
+                tokens.push(format!("{{{comment}}}"));
+            }
+
+            if current_white != first_white {
+                number += 1;
+            }
+            current_white = !current_white;
+        }
+
+        tokens.push(self.result.clone());
+
+        let mut line_len = 0;
+        for token in tokens {
+            if line_len > 0 && line_len + 1 + token.len() > LINE_WIDTH {
+                writeln!(f)?;
+                line_len = 0;
+            } else if line_len > 0 {
+                write!(f, " ")?;
+                line_len += 1;
+            }
+
+            write!(f, "{token}")?;
+            line_len += token.len();
+        }
+
+        writeln!(f)
+    }
+}
+
+/// Parses all games in a PDN file.
+///
+/// Moves are validated against the legal moves of the position they are played in.
+/// Move numbers, `!`/`?` annotations and variations are skipped.
+///
+/// # Errors
+///
+/// - [`Error::Pdn`][0] if the text is malformed or a move is not legal,
+/// - any error of [`Board::from_fen`] for an invalid `FEN` tag.
+///
+/// [0]: crate::Error::Pdn
+pub fn parse(text: &str) -> Result<Vec<Record>, crate::Error> {
+    let mut parser = Parser {
+        chars: text.char_indices().peekable(),
+        text,
+    };
+    let mut games = Vec::new();
+
+    while let Some(game) = parser.game()? {
+        games.push(game);
+    }
+
+    Ok(games)
+}
+
+/// Returns the rules of a `GameType` tag value, e.g. `25` or `21,B,8,8,N1,0`.
+fn game_type_rules(game_type: &str) -> Result<Rules, Error> {
+    let number = game_type.split(',').next().unwrap_or_default().trim();
+
+    number
+        .parse()
+        .ok()
+        .and_then(Variant::from_game_type)
+        .map(Variant::rules)
+        .ok_or_else(|| Error::Syntax(format!("unsupported GameType {game_type:?}")))
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    /// Parses the next game, returning `None` at the end of the text.
+    fn game(&mut self) -> Result<Option<Record>, crate::Error> {
+        let mut record = Record::default();
+        let mut tags = Vec::new();
+
+        self.skip_whitespace();
+        while self.chars.next_if(|(_, c)| *c == '[').is_some() {
+            tags.push(self.tag()?);
+            self.skip_whitespace();
+        }
+
+        if tags.is_empty() && self.chars.peek().is_none() {
+            return Ok(None);
+        }
+
+        let rules = match tags.iter().find(|(name, _)| name == "GameType") {
+            Some((_, game_type)) => game_type_rules(game_type)?,
+            None => Rules::default(),
+        };
+
+        record.start = BoardBuilder::initial(rules).build();
+        record.start_white = rules.white_moves_first;
+
+        if let Some((_, fen)) = tags.iter().find(|(name, _)| name == "FEN") {
+            (record.start, record.start_white) = Board::from_fen_with_rules(fen, rules)?;
+        }
+        record.tags = tags;
+
+        let mut board = record.start.clone();
+        let mut current_white = record.start_white;
+
+        while let Some(&(_, c)) = self.chars.peek() {
+            match c {
+                '[' => break,
+                '{' | ';' => {
+                    self.chars.next();
+                    let comment = if c == '{' {
+                        self.until('}')?
+                    } else {
+                        self.until('\n').unwrap_or_default()
+                    };
+
+                    let comment = comment.trim().to_owned();
+                    match record.plies.last_mut() {
+                        Some(ply) => ply.comment = Some(comment),
+                        None => record.comment = Some(comment),
+                    }
+                }
+                '(' => self.variation()?,
+                ')' | '}' | ']' => Err(Error::Syntax(format!("unbalanced {c:?}")))?,
+                '$' => {
+                    self.chars.next();
+                    let nag = self.token();
+                    let nag = nag
+                        .parse()
+                        .map_err(|_| Error::Syntax(format!("invalid NAG ${nag}")))?;
+
+                    match record.plies.last_mut() {
+                        Some(ply) => ply.nags.push(nag),
+                        None => Err(Error::Syntax(format!("NAG ${nag} before first move")))?,
+                    }
+                }
+                _ if c.is_whitespace() => self.skip_whitespace(),
+                _ => {
+                    let token = self.token();
+
+                    if RESULTS.contains(&token.as_str()) {
+                        record.result = token;
+                        break;
+                    }
+
+                    // strip move numbers ("1.", "12...", "3.22-18") and annotations
+                    let notation = token
+                        .rsplit('.')
+                        .next()
+                        .unwrap_or_default()
+                        .trim_end_matches(['!', '?']);
+
+                    if notation.is_empty() {
+                        continue;
+                    }
+
+                    let checkers_move =
+                        board
+                            .parse_move(notation, current_white)
+                            .map_err(|source| Error::Move {
+                                ply: record.plies.len() + 1,
+                                source,
+                            })?;
+
+                    board.apply_move_unchecked(&checkers_move);
+                    current_white = !current_white;
+
+                    record.plies.push(Ply::new(checkers_move));
+                }
+            }
+        }
+
+        if let Some(result) = record.tag("Result") {
+            if record.result == "*" {
+                record.result = result.to_owned();
+            }
+        }
+
+        Ok(Some(record))
+    }
+
+    /// Parses a `Name "Value"]` tag after its opening bracket.
+    fn tag(&mut self) -> Result<(String, String), Error> {
+        let tag = self.until(']')?;
+        let invalid = || Error::Syntax(format!("invalid tag [{tag}]"));
+
+        let (name, value) = tag
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(invalid)?;
+        let value = value
+            .trim()
+            .strip_prefix('"')
+            .and_then(|i| i.strip_suffix('"'))
+            .ok_or_else(invalid)?;
+
+        Ok((
+            name.to_owned(),
+            value.replace("\\\"", "\"").replace("\\\\", "\\"),
+        ))
+    }
+
+    /// Skips a possibly nested variation starting at the next `(`.
+    fn variation(&mut self) -> Result<(), Error> {
+        let mut depth = 0;
+
+        for (_, c) in self.chars.by_ref() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+
+        Err(Error::Syntax("unterminated variation".to_owned()))
+    }
+
+    /// Consumes characters up to and including `end`, returning the ones before it.
+    fn until(&mut self, end: char) -> Result<String, Error> {
+        let start = self.chars.peek().map_or(self.text.len(), |(i, _)| *i);
+
+        for (i, c) in self.chars.by_ref() {
+            if c == end {
+                return Ok(self.text[start..i].to_owned());
+            }
+        }
+
+        Err(Error::Syntax(format!("missing {end:?}")))
+    }
+
+    /// Consumes a move text token.
+    fn token(&mut self) -> String {
+        let mut token = String::new();
+
+        while let Some((_, c)) = self
+            .chars
+            .next_if(|(_, c)| !c.is_whitespace() && !"[]{}();$".contains(*c))
+        {
+            token.push(c);
+        }
+
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    const GAMES: &str = r#"[Event "Club championship"]
+[White "Alice"]
+[Black "Bob \"the Brick\""]
+[Result "0-2"]
+
+{Opening} 1. 22-18 11-15 2. 18x11 {forced} 8x15 $1 3.d2-c3! (3. 21-17 (3. 23-19) 9-13)
+15-19 4. 24x15 10x17 0-2
+
+[FEN "B:W18,K30:B1,2"]
+
+1... 1-5 2. 30-26 ; king steps
+2-6 *
+"#;
+
+    #[test]
+    fn test_parse() -> Result<(), crate::Error> {
+        let games = parse(GAMES)?;
+        assert_eq!(games.len(), 2);
+
+        let first = &games[0];
+        assert_eq!(first.tag("Black"), Some("Bob \"the Brick\""));
+        assert_eq!(first.comment.as_deref(), Some("Opening"));
+        assert_eq!(first.plies.len(), 8);
+        assert_eq!(first.plies[2].comment.as_deref(), Some("forced"));
+        assert_eq!(first.plies[3].nags, [1]);
+        assert_eq!(format!("{}", first.plies[7].checkers_move), "10x19x26x17");
+        assert_eq!(first.result, "0-2");
+
+        let second = &games[1];
+        assert!(!second.start_white);
+        assert_eq!(second.plies.len(), 3);
+        assert_eq!(second.plies[1].comment.as_deref(), Some("king steps"));
+        assert_eq!(second.result, "*");
+        assert_eq!(second.final_position()?, Board::from_fen("W:W18,K26:B5,6")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_game_type() -> Result<(), crate::Error> {
+        let game = "[GameType \"23\"]\n\n1. 11-15 22-18 2. 15x22 *\n".parse::<Record>()?;
+
+        assert_eq!(game.start.rules(), Variant::Pool.rules());
+        assert!(!game.start_white);
+        assert_eq!(
+            game.to_string(),
+            "[GameType \"23\"]\n\n1. 11-15 22-18 2. 15x22 *\n"
+        );
+
+        let game = "[GameType \"20\"]\n\n1. 32-28 19-23 2. 28x19 14x23 *".parse::<Record>()?;
+
+        assert_eq!(game.start.rules(), Variant::International.rules());
+        assert_eq!(game.final_position()?.0.pieces().len(), 38);
+
+        parse("[GameType \"99\"]\n\n*").expect_err("parsed unknown game type");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let invalid = [
+            "[Event \"unterminated]",
+            "[Event]",
+            "1. 22-18 {unterminated",
+            "1. 22-18 (11-15",
+            "1. 22-19",
+            "1. 22-18 22-17",
+            "$1 1. 22-18",
+            "1. 22-18 ) 11-15",
+            "1. 22-18 } 11-15",
+            "1. 22-18 ] 11-15",
+        ];
+
+        for text in invalid {
+            parse(text).expect_err(text);
+        }
+
+        assert!(matches!(
+            parse("1. 22-18 18-14"),
+            Err(crate::Error::Pdn(Error::Move { ply: 2, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_write() -> Result<(), crate::Error> {
+        for game in parse(GAMES)? {
+            let written = game.to_string();
+            assert_eq!(written.parse::<Record>()?, game);
+        }
+
+        let second = &parse(GAMES)?[1];
+        assert_eq!(
+            second.to_string(),
+            "[FEN \"B:W18,K30:B1,2\"]\n\n1... 1-5 2. 30-26 {king steps} 2-6 *\n"
+        );
+
+        let mut record = Record::new(Board::from_fen("W:W18:B1")?.0, true);
+        record.set_tag("Event", "Test");
+        record.set_tag("Event", "Final");
+        record
+            .plies
+            .push(Ply::new(record.start.legal_moves(true)[0].clone()));
+
+        assert_eq!(
+            record.to_string(),
+            "[Event \"Final\"]\n[FEN \"W:W18:B1\"]\n\n1. 18-14 *\n"
+        );
+
+        // the automatic FEN tag is followed by a blank line as well
+        let record = Record::new(Board::from_fen("W:W18:B1")?.0, true);
+        assert_eq!(record.to_string(), "[FEN \"W:W18:B1\"]\n\n*\n");
+        assert_eq!(record.to_string().parse::<Record>()?.start, record.start);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_variants() -> Result<(), crate::Error> {
+        for variant in Variant::ALL {
+            let rules = variant.rules();
+            let mut record = Record::new(
+                BoardBuilder::initial(rules).build(),
+                rules.white_moves_first,
+            );
+            let (mut board, mut current_white) = (record.start.clone(), record.start_white);
+
+            for _ in 0..4 {
+                let checkers_move = board.legal_moves(current_white)[0].clone();
+                board.apply_move(&checkers_move)?;
+                current_white = !current_white;
+                record.plies.push(Ply::new(checkers_move));
+            }
+
+            let written = record.to_string();
+            let parsed = written.parse::<Record>()?;
+
+            assert_eq!(parsed.start, record.start, "{variant:?}");
+            assert_eq!(parsed.start_white, record.start_white, "{variant:?}");
+            assert_eq!(parsed.plies, record.plies, "{variant:?}");
+            assert_eq!(
+                parsed.tag("GameType").is_some(),
+                variant != Variant::Russian,
+                "{variant:?}"
+            );
+
+            // a custom start position keeps the rules as well
+            let mut record = Record::new(board, current_white);
+            record.set_tag("Event", "Test");
+            let parsed = record.to_string().parse::<Record>()?;
+
+            assert_eq!(parsed.start, record.start, "{variant:?}");
+            assert_eq!(parsed.start_white, record.start_white, "{variant:?}");
+        }
+
+        Ok(())
+    }
+}
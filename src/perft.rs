@@ -0,0 +1,96 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{Board, CheckersMove, MoveList};
+
+/// Per-move node counts returned by [`Board::perft_divide`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divide {
+    pub moves: Vec<(CheckersMove, u64)>,
+}
+
+impl Divide {
+    /// Total number of leaf nodes over all moves.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.moves.iter().map(|(_, nodes)| nodes).sum()
+    }
+}
+
+/// Prints one `move: nodes` line per move, followed by the total.
+impl Display for Divide {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (checkers_move, nodes) in &self.moves {
+            writeln!(f, "{checkers_move}: {nodes}")?;
+        }
+
+        write!(f, "total: {}", self.total())
+    }
+}
+
+impl Board {
+    /// Counts the leaf nodes of the legal move tree of depth `depth` with
+    /// `current_white` to move.
+    ///
+    /// The tree is walked with [`Self::generate_moves`] and
+    /// [`Self::apply_compact_move`], which produce the same moves as
+    /// [`Self::legal_moves`] and [`Self::apply_move`] without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use checkers_lib::*;
+    /// let board = BoardBuilder::default().build();
+    ///
+    /// assert_eq!(board.perft(3, true), 302);
+    /// ```
+    #[must_use]
+    pub fn perft(&self, depth: u32, current_white: bool) -> u64 {
+        perft(&mut self.clone(), depth, current_white)
+    }
+
+    /// Same as [`Self::perft`], but returns the node count below every legal move
+    /// separately.
+    ///
+    /// Comparing divide output against a reference implementation is the quickest way
+    /// to find which move a generation bug hides behind.
+    #[must_use]
+    pub fn perft_divide(&self, depth: u32, current_white: bool) -> Divide {
+        let mut board = self.clone();
+
+        let moves = self
+            .legal_moves(current_white)
+            .into_iter()
+            .map(|checkers_move| {
+                let undo = board.apply_move_unchecked(&checkers_move);
+                let nodes = perft(&mut board, depth.saturating_sub(1), !current_white);
+                board.unapply_move_unchecked(&checkers_move, undo);
+
+                (checkers_move, nodes)
+            })
+            .collect();
+
+        Divide { moves }
+    }
+}
+
+fn perft(board: &mut Board, depth: u32, current_white: bool) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let mut list = MoveList::new();
+    board.generate_moves(current_white, &mut list);
+
+    if depth == 1 {
+        return list.len() as u64;
+    }
+
+    let mut nodes = 0;
+    for compact in &list {
+        let undo = board.apply_compact_move(*compact);
+        nodes += perft(board, depth - 1, !current_white);
+        board.unapply_compact_move(*compact, undo);
+    }
+
+    nodes
+}
@@ -1,10 +1,7 @@
-use std::collections::HashSet;
+use crate::{movegen, Board, CheckersMove, Position, RulesError};
 
-use itertools::iproduct;
-
-use crate::{Board, CheckersMove, Position, RulesError};
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Piece {
     pub is_king: bool,
     pub is_white: bool,
@@ -50,17 +47,18 @@ impl Piece {
             Err(RulesError::Occupied(new_pos))?;
         }
 
-        let mut moves = vec![CheckersMove {
-            old: self,
-            new: Piece {
+        let mut moves = vec![CheckersMove::arbitrary_with_path(
+            self,
+            Piece {
                 is_king: self.is_king || new_pos.is_promoting(self),
                 is_white: self.is_white,
                 position: new_pos,
             },
-            captures: HashSet::new(),
-        }];
+            vec![new_pos],
+            Vec::new(),
+        )];
 
-        if self.is_king {
+        if self.is_king && board.rules().flying_kings {
             while let Ok(temp) = new_pos.increment(direction) {
                 new_pos = temp;
 
@@ -70,6 +68,7 @@ impl Piece {
 
                 let mut next_move = moves[0].clone();
                 next_move.new.position = new_pos;
+                next_move.path = vec![new_pos];
 
                 moves.push(next_move);
             }
@@ -86,20 +85,31 @@ impl Piece {
     /// - [`Error::Rules(Empty)`][1] if there is nothing to capture in given `direction`,
     /// - [`Error::Rules(Occupied)`][2] if the tile after capture is occupied,
     /// - [`Error::Position(OutOfBounds)`][3] if `direction` points immediatly out of bounds or
-    ///   there is no tile to land on after capture.
+    ///   there is no tile to land on after capture,
+    /// - [`Error::Rules(NotKing)`][4] if trying to capture backwards or capture a king
+    ///   without being a king when the [`Rules`][5] don't allow it.
     ///
     /// [0]: crate::RulesError::SameColorCapture
     /// [1]: crate::RulesError::Empty
     /// [2]: crate::RulesError::Occupied
     /// [3]: crate::position::Error::OutOfBounds
+    /// [4]: crate::RulesError::NotKing
+    /// [5]: crate::Rules
     pub fn capture_in_direction(
         self,
         board: &Board,
         direction: (i8, i8),
     ) -> Result<Vec<CheckersMove>, crate::Error> {
+        if !self.is_king
+            && !board.rules().men_capture_backwards
+            && ((direction.1 > 0) == self.is_white)
+        {
+            Err(RulesError::NotKing(self.position))?;
+        }
+
         let mut capture_pos = self.position.increment(direction)?;
 
-        if self.is_king {
+        if self.is_king && board.rules().flying_kings {
             while board.get_tile(capture_pos).is_none() {
                 let Ok(next_capture_pos) = capture_pos.increment(direction) else {
                     Err(RulesError::Empty(capture_pos))?
@@ -123,22 +133,27 @@ impl Piece {
                         is_white: self.is_white,
                     })?;
                 }
+
+                if !self.is_king && other.is_king && !board.rules().men_capture_kings {
+                    Err(RulesError::NotKing(self.position))?;
+                }
             }
         }
 
         let mut captures = Vec::new();
 
-        captures.push(CheckersMove {
-            old: self,
-            new: Piece {
+        captures.push(CheckersMove::arbitrary_with_path(
+            self,
+            Piece {
                 is_king: self.is_king || new_pos.is_promoting(self),
                 is_white: self.is_white,
                 position: new_pos,
             },
-            captures: HashSet::from([capture_pos]),
-        });
+            vec![new_pos],
+            vec![capture_pos],
+        ));
 
-        if self.is_king {
+        if self.is_king && board.rules().flying_kings {
             while let Ok(temp) = new_pos.increment(direction) {
                 new_pos = temp;
                 if board.get_tile(new_pos).is_some() {
@@ -147,6 +162,7 @@ impl Piece {
 
                 let mut next_move = captures[0].clone();
                 next_move.new.position = new_pos;
+                next_move.path = vec![new_pos];
 
                 captures.push(next_move);
             }
@@ -155,62 +171,15 @@ impl Piece {
         Ok(captures)
     }
 
+    /// Returns all moves of `self` on `board`: every complete capture sequence, or every
+    /// non-capturing move if `self` can't capture.
     #[must_use]
-    fn capture_in_all_directions(self, board: &Board) -> Vec<CheckersMove> {
-        let mut all_captures = Vec::new();
-
-        for direction in iproduct!([-1, 1], [-1, 1]) {
-            if let Ok(mut captures) = self.capture_in_direction(board, direction) {
-                all_captures.append(&mut captures);
-            }
-        }
-
-        all_captures
-    }
-
-    #[must_use]
-    #[allow(clippy::missing_panics_doc)]
     pub fn all_possible_moves(self, board: &Board) -> Vec<CheckersMove> {
-        let possible_captures = self.capture_in_all_directions(board);
         let mut all_possible_moves = Vec::new();
 
-        // if piece can't capture
-        if possible_captures.is_empty() {
-            for direction in iproduct!([-1, 1], [-1, 1]) {
-                if let Ok(mut moves) = self.moves(board, direction) {
-                    all_possible_moves.append(&mut moves);
-                }
-            }
-
-            return all_possible_moves;
-        }
-
-        // if piece can capture
-        for current_capture in possible_captures {
-            let board_next = board.clone().applied_move_unchecked(&current_capture);
-            let piece_next =
-                unsafe { board_next.get_tile_unchecked(current_capture.new_piece().position) };
-            // SAFETY: ↑ tile at `capture.new.position` in `board_next` is presumed to never be empty
-
-            let mut recursive_captures = piece_next
-                .all_possible_moves(&board_next)
-                .into_iter()
-                .filter(|i| !i.captures.is_empty())
-                .collect::<Vec<_>>();
-
-            for recursive_capture in &mut recursive_captures {
-                recursive_capture.old = current_capture.old_piece();
-                recursive_capture
-                    .captures
-                    .extend(current_capture.captures.clone());
-            }
-
-            if recursive_captures.is_empty() {
-                recursive_captures.push(current_capture);
-            }
-
-            all_possible_moves.append(&mut recursive_captures);
-        }
+        movegen::piece_moves(board, self, &mut |compact, path| {
+            all_possible_moves.push(compact.with_path(self, path));
+        });
 
         all_possible_moves
     }
@@ -249,3 +218,36 @@ impl std::fmt::Display for Piece {
         )
     }
 }
+
+/// Serialized form of a [`Piece`] of a [`Board`] or [`CheckersMove`], with its square
+/// number resolved on the board size they store.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct PieceRepr {
+    is_king: bool,
+    is_white: bool,
+    position: u8,
+}
+
+#[cfg(feature = "serde")]
+impl PieceRepr {
+    pub(crate) fn try_into_piece(
+        self,
+        size: crate::BoardSize,
+    ) -> Result<Piece, crate::position::Error> {
+        let position = Position::try_new(self.position, size)?;
+
+        Ok(Piece::new(self.is_king, self.is_white, position))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Piece> for PieceRepr {
+    fn from(piece: Piece) -> Self {
+        Self {
+            is_king: piece.is_king,
+            is_white: piece.is_white,
+            position: piece.position.into(),
+        }
+    }
+}
@@ -4,17 +4,119 @@ use crate::Piece;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
-    #[error("position out of bounds (must be in 1..=32 or (0..8, 0..8))")]
+    #[error("position out of bounds of the board")]
     OutOfBounds,
 
     #[error("position points to white square")]
     WhiteSquare,
 }
 
-#[derive(Clone, Copy)]
-pub struct Position(u8);
+/// Number of rows and columns of a board.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoardSize {
+    /// 8x8 board with 32 playable squares.
+    #[default]
+    Eight,
+
+    /// 10x10 board with 50 playable squares.
+    Ten,
+}
+
+impl BoardSize {
+    /// Number of rows (and columns) of the board.
+    #[must_use]
+    pub const fn rows(self) -> u8 {
+        match self {
+            BoardSize::Eight => 8,
+            BoardSize::Ten => 10,
+        }
+    }
+
+    /// Number of playable (dark) squares of the board.
+    #[must_use]
+    pub const fn squares(self) -> u8 {
+        self.rows() * self.rows() / 2
+    }
+
+    /// Number of pieces each color starts with, filling all but the two middle rows.
+    #[must_use]
+    pub const fn pieces(self) -> u8 {
+        (self.rows() / 2 - 1) * self.rows() / 2
+    }
+}
+
+/// Playable square of a board, numbered from 1 in the top left to
+/// [`BoardSize::squares`] in the bottom right.
+///
+/// The `TryFrom` impls create squares of an 8x8 board, use [`Self::try_new`] or
+/// [`Self::try_from_coords`] for other sizes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    num: u8,
+    size: BoardSize,
+}
 
 impl Position {
+    /// Creates a `Position` from a square number without checking that it is in bounds.
+    pub(crate) const fn new_unchecked(num: u8, size: BoardSize) -> Self {
+        Position { num, size }
+    }
+
+    /// Tries to create a `Position` from square number `num` of a board of `size`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::OutOfBounds`] if `num` is not in `1..=size.squares()`.
+    pub fn try_new(num: u8, size: BoardSize) -> Result<Self, Error> {
+        if num == 0 || num > size.squares() {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(Position { num, size })
+    }
+
+    /// Tries to create a `Position` from `(x, y)` coordinates of a board of `size`, with
+    /// `(0, 0)` in the top left.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::OutOfBounds`] if `x` or `y` is not in `0..size.rows()`,
+    /// - [`Error::WhiteSquare`] if the coordinates point to a white square.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn try_from_coords((x, y): (usize, usize), size: BoardSize) -> Result<Self, Error> {
+        let rows = usize::from(size.rows());
+
+        if x >= rows || y >= rows {
+            return Err(Error::OutOfBounds);
+        }
+
+        if (x + y) % 2 == 0 {
+            return Err(Error::WhiteSquare);
+        }
+
+        Ok(Position {
+            num: (y * rows / 2 + x / 2 + 1) as u8,
+            size,
+        })
+    }
+
+    /// Returns the `(x, y)` coordinates of `self`, with `(0, 0)` in the top left.
+    #[must_use]
+    pub fn coords(self) -> (usize, usize) {
+        let half = self.size.rows() / 2;
+        let y = (self.num - 1) / half;
+        let x = (self.num - 1) % half * 2 + (y + 1) % 2;
+
+        (usize::from(x), usize::from(y))
+    }
+
+    /// Size of the board `self` is a square of.
+    #[must_use]
+    pub fn size(self) -> BoardSize {
+        self.size
+    }
+
     /// Return `self` with `x` and `y` incremented by `delta.0` and `delta.1`
     /// respectively.
     ///
@@ -28,96 +130,104 @@ impl Position {
     #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::cast_possible_wrap)]
     pub fn increment(self, (dx, dy): (i8, i8)) -> Result<Self, Error> {
-        let (x, y): (usize, usize) = self.into();
+        let (x, y) = self.coords();
         let (x, y): (i8, i8) = (x as i8 + dx, y as i8 + dy);
+        let rows = self.size.rows() as i8;
 
-        match (x, y) {
-            (0..=7, 0..=7) => Ok((x as usize, y as usize).try_into()?),
-            _ => Err(Error::OutOfBounds),
+        if (0..rows).contains(&x) && (0..rows).contains(&y) {
+            Self::try_from_coords((x as usize, y as usize), self.size)
+        } else {
+            Err(Error::OutOfBounds)
         }
     }
 
     /// Returns if a piece on this tile should be promoted.
     #[must_use]
     pub fn is_promoting(self, piece: Piece) -> bool {
-        matches!((piece.is_white, self.0), (true, 1..=4) | (false, 29..=32))
+        let half = self.size.rows() / 2;
+
+        if piece.is_white {
+            self.num <= half
+        } else {
+            self.num > self.size.squares() - half
+        }
+    }
+
+    /// Returns if `self` lies on the main diagonal (the main road), running from the
+    /// bottom left corner to the top right one, e.g. `a1-h8` on an 8x8 board.
+    #[must_use]
+    pub fn is_main_diagonal(self) -> bool {
+        let (x, y) = self.coords();
+
+        x + y + 1 == usize::from(self.size.rows())
     }
 }
 
-/// Tries to convert to a `Position` from a `u8` square number (draughts notation).
+/// Tries to convert to a `Position` from a `u8` square number (draughts notation) of an
+/// 8x8 board.
 impl TryFrom<u8> for Position {
     type Error = Error;
 
     fn try_from(num: u8) -> Result<Self, Self::Error> {
-        match num {
-            0 | 33.. => Err(Error::OutOfBounds),
-            _ => Ok(Position(num)),
-        }
+        Self::try_new(num, BoardSize::Eight)
     }
 }
 
-/// Tries to convert to a `Position` from a coordinate tuple.
+/// Tries to convert to a `Position` from a coordinate tuple of an 8x8 board.
 impl TryFrom<(usize, usize)> for Position {
     type Error = Error;
 
-    #[allow(clippy::cast_possible_truncation)]
     fn try_from(coords: (usize, usize)) -> Result<Self, Self::Error> {
-        match coords {
-            (8.., 8..) => Err(Error::OutOfBounds),
-            (x, y) => {
-                if (x + y) % 2 == 0 {
-                    Err(Error::WhiteSquare)
-                } else {
-                    Ok(Position((y as u8) * 4 + (x as u8) / 2 + 1))
-                }
-            }
-        }
+        Self::try_from_coords(coords, BoardSize::Eight)
     }
 }
 
 /// Converts a Position to a u8 square number (draughts notation).
 impl From<Position> for u8 {
-    fn from(Position(num): Position) -> Self {
-        num
+    fn from(pos: Position) -> Self {
+        pos.num
     }
 }
 
 impl From<Position> for (usize, usize) {
-    fn from(Position(num): Position) -> Self {
-        let y = (num - 1) / 4;
-        let x = (num - 1) % 4 * 2 + (y + 1) % 2;
-
-        (usize::from(x), usize::from(y))
+    fn from(pos: Position) -> Self {
+        pos.coords()
     }
 }
 
-impl PartialEq for Position {
-    fn eq(&self, other: &Self) -> bool {
-        let s: u8 = (*self).into();
-        let o: u8 = (*other).into();
-
-        s == o
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.num)
     }
 }
 
-impl Eq for Position {}
-
-impl std::hash::Hash for Position {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.0.hash(state);
+impl std::fmt::Debug for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.coords())
     }
 }
 
-impl std::fmt::Display for Position {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+/// Serializes a `Position` as its square number.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Position {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.num)
     }
 }
 
-impl std::fmt::Debug for Position {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let coords: (usize, usize) = (*self).into();
-        write!(f, "{coords:?}")
+/// Deserializes a `Position` from a square number of an 8x8 board, like `TryFrom<u8>`.
+///
+/// [`Board`][0] and [`CheckersMove`][1] store their board size, so their squares are
+/// deserialized on boards of any size.
+///
+/// [0]: crate::Board
+/// [1]: crate::CheckersMove
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Position {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let num = <u8 as serde::Deserialize>::deserialize(deserializer)?;
+
+        Self::try_from(num).map_err(serde::de::Error::custom)
     }
 }
 
@@ -139,7 +249,7 @@ mod tests {
             TryInto::<Position>::try_into(coords)?;
         }
 
-        for coords in iproduct!(8..15usize, 8..15usize) {
+        for coords in iproduct!(0..15usize, 0..15usize).filter(|(x, y)| *x >= 8 || *y >= 8) {
             TryInto::<Position>::try_into(coords).expect_err("creates out of bounds position");
         }
 
@@ -181,6 +291,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ten() -> Result<(), Error> {
+        let size = BoardSize::Ten;
+
+        for num in 1..=50 {
+            let pos = Position::try_new(num, size)?;
+            assert_eq!(Position::try_from_coords(pos.coords(), size)?, pos);
+        }
+
+        Position::try_new(51, size).expect_err("creates out of bounds position");
+        assert_eq!(Position::try_new(46, size)?.coords(), (0, 9));
+        assert_eq!(
+            Position::try_new(5, size)?.increment((-1, 1))?,
+            Position::try_new(10, size)?
+        );
+        Position::try_new(5, size)?
+            .increment((1, 1))
+            .expect_err("incremented out of bounds");
+
+        assert_ne!(Position::try_new(1, size)?, 1.try_into()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_diagonals() -> Result<(), Error> {
+        let main = [29, 25, 22, 18, 15, 11, 8, 4];
+
+        for num in 1..=32u8 {
+            let pos: Position = num.try_into()?;
+
+            assert_eq!(pos.is_main_diagonal(), main.contains(&num), "{num}");
+        }
+
+        assert!(Position::try_new(46, BoardSize::Ten)?.is_main_diagonal());
+        assert!(Position::try_new(5, BoardSize::Ten)?.is_main_diagonal());
+
+        Ok(())
+    }
+
     #[test]
     fn test_eq() -> Result<(), crate::Error> {
         let a: Position = 1.try_into()?;
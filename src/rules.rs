@@ -0,0 +1,279 @@
+use crate::BoardSize;
+
+/// Rules of a draughts variant, consulted by [`Board`][0] move generation and
+/// [`BoardBuilder`][1].
+///
+/// Every [`Board`][0] carries its rules. Use the presets of [`Variant`] for the common
+/// variants, or adjust the fields to play a custom one.
+///
+/// # Examples
+///
+/// ```
+/// # use checkers_lib::*;
+/// let rules = Rules {
+///     flying_kings: false,
+///     ..Variant::Russian.rules()
+/// };
+/// let board = BoardBuilder::initial(rules).build();
+///
+/// assert_eq!(board.rules(), rules);
+/// ```
+///
+/// [0]: crate::Board
+/// [1]: crate::BoardBuilder
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Rules {
+    /// Size of the board, which also sets the number of pieces per color.
+    pub board_size: BoardSize,
+
+    /// Kings move and capture any distance along a diagonal, instead of one square.
+    pub flying_kings: bool,
+
+    /// Men may capture backwards as well as forwards.
+    pub men_capture_backwards: bool,
+
+    /// Men may capture kings, otherwise only kings can.
+    pub men_capture_kings: bool,
+
+    /// White makes the first move of the game, otherwise black does.
+    pub white_moves_first: bool,
+
+    /// What happens when a man reaches the promoting row in the middle of a capture, which
+    /// is also reflected by the king status of [`CheckersMove::new_piece`](crate::CheckersMove::new_piece).
+    pub capture_promotion: CapturePromotion,
+
+    /// Which of the available capture sequences may be chosen.
+    pub capture_priority: CapturePriority,
+
+    /// The game is drawn when the same position occurs for the third time with the same
+    /// side to move.
+    pub repetition_draw: bool,
+
+    /// The game is drawn after this many moves of each side without a capture or a man
+    /// being moved.
+    pub king_moves_draw: Option<u8>,
+
+    /// Russian endgame draws: endings with only kings on both sides are drawn after 5, 30
+    /// or 60 moves without a capture depending on the number of pieces (2-3, 4-5 or 6-7),
+    /// and three or more kings against a lone king on the main road after 15 moves.
+    pub endgame_draws: bool,
+}
+
+/// What happens when a man reaches the promoting row in the middle of a capture, see
+/// [`Rules::capture_promotion`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CapturePromotion {
+    /// The man is promoted and continues capturing as a king.
+    Continue,
+
+    /// The man is promoted and the capture ends there.
+    End,
+
+    /// The man continues capturing as a man, and is only promoted if the capture ends
+    /// on the promoting row.
+    PassThrough,
+}
+
+/// Which of the available capture sequences may be chosen, see
+/// [`Rules::capture_priority`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CapturePriority {
+    /// Any capture sequence may be chosen.
+    Any,
+
+    /// Only the sequences capturing the most pieces may be chosen.
+    Majority,
+
+    /// Only the sequences capturing the most pieces may be chosen, and among them the
+    /// ones capturing the most kings (Spanish rule).
+    MajorityKings,
+
+    /// Only the sequences capturing the most pieces may be chosen, and among them in
+    /// turn the ones made by a king, capturing the most kings and capturing a king
+    /// earliest (Italian rule).
+    Italian,
+}
+
+impl Default for Rules {
+    /// Russian draughts rules.
+    fn default() -> Self {
+        Variant::Russian.rules()
+    }
+}
+
+/// Draughts variants with preset [`Rules`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variant {
+    /// Russian draughts: flying kings, men capture backwards, white moves first.
+    Russian,
+
+    /// American pool checkers: flying kings, men capture backwards, black moves first and
+    /// men only get promoted at the end of a capture.
+    Pool,
+
+    /// English draughts (American checkers): kings move one square, men only capture
+    /// forwards, black moves first and promotion ends a capture.
+    English,
+
+    /// International draughts: 10x10 board, flying kings, men capture backwards, white
+    /// moves first, the capture of the most pieces is mandatory and men only get
+    /// promoted at the end of a capture.
+    International,
+
+    /// Brazilian draughts: the rules of international draughts on an 8x8 board.
+    Brazilian,
+
+    /// Italian draughts: kings move one square, men only capture forwards and can't
+    /// capture kings, white moves first and [`CapturePriority::Italian`] applies.
+    ///
+    /// Italian boards are mirrored, but squares are numbered as in the other variants.
+    Italian,
+
+    /// Spanish draughts: flying kings, men only capture forwards, white moves first and
+    /// [`CapturePriority::MajorityKings`] applies.
+    ///
+    /// Spanish boards are mirrored, but squares are numbered as in the other variants.
+    Spanish,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 7] = [
+        Variant::Russian,
+        Variant::Pool,
+        Variant::English,
+        Variant::International,
+        Variant::Brazilian,
+        Variant::Italian,
+        Variant::Spanish,
+    ];
+
+    #[must_use]
+    pub fn rules(self) -> Rules {
+        match self {
+            Variant::Russian => Rules {
+                board_size: BoardSize::Eight,
+                flying_kings: true,
+                men_capture_backwards: true,
+                men_capture_kings: true,
+                white_moves_first: true,
+                capture_promotion: CapturePromotion::Continue,
+                capture_priority: CapturePriority::Any,
+                repetition_draw: true,
+                king_moves_draw: Some(15),
+                endgame_draws: true,
+            },
+            Variant::Pool => Rules {
+                board_size: BoardSize::Eight,
+                flying_kings: true,
+                men_capture_backwards: true,
+                men_capture_kings: true,
+                white_moves_first: false,
+                capture_promotion: CapturePromotion::PassThrough,
+                capture_priority: CapturePriority::Any,
+                repetition_draw: true,
+                king_moves_draw: None,
+                endgame_draws: false,
+            },
+            Variant::English => Rules {
+                board_size: BoardSize::Eight,
+                flying_kings: false,
+                men_capture_backwards: false,
+                men_capture_kings: true,
+                white_moves_first: false,
+                capture_promotion: CapturePromotion::End,
+                capture_priority: CapturePriority::Any,
+                repetition_draw: true,
+                king_moves_draw: Some(40),
+                endgame_draws: false,
+            },
+            Variant::International => Rules {
+                board_size: BoardSize::Ten,
+                flying_kings: true,
+                men_capture_backwards: true,
+                men_capture_kings: true,
+                white_moves_first: true,
+                capture_promotion: CapturePromotion::PassThrough,
+                capture_priority: CapturePriority::Majority,
+                repetition_draw: true,
+                king_moves_draw: Some(25),
+                endgame_draws: false,
+            },
+            Variant::Brazilian => Rules {
+                board_size: BoardSize::Eight,
+                ..Variant::International.rules()
+            },
+            Variant::Italian => Rules {
+                board_size: BoardSize::Eight,
+                flying_kings: false,
+                men_capture_backwards: false,
+                men_capture_kings: false,
+                white_moves_first: true,
+                capture_promotion: CapturePromotion::End,
+                capture_priority: CapturePriority::Italian,
+                repetition_draw: true,
+                king_moves_draw: None,
+                endgame_draws: false,
+            },
+            Variant::Spanish => Rules {
+                board_size: BoardSize::Eight,
+                flying_kings: true,
+                men_capture_backwards: false,
+                men_capture_kings: true,
+                white_moves_first: true,
+                capture_promotion: CapturePromotion::End,
+                capture_priority: CapturePriority::MajorityKings,
+                repetition_draw: true,
+                king_moves_draw: None,
+                endgame_draws: false,
+            },
+        }
+    }
+
+    /// Returns the PDN `GameType` tag number of the variant.
+    #[must_use]
+    pub fn game_type(self) -> u8 {
+        match self {
+            Variant::International => 20,
+            Variant::English => 21,
+            Variant::Italian => 22,
+            Variant::Pool => 23,
+            Variant::Spanish => 24,
+            Variant::Russian => 25,
+            Variant::Brazilian => 26,
+        }
+    }
+
+    /// Returns the variant with PDN `GameType` tag number `game_type`.
+    #[must_use]
+    pub fn from_game_type(game_type: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|i| i.game_type() == game_type)
+    }
+}
+
+impl From<Variant> for Rules {
+    fn from(variant: Variant) -> Self {
+        variant.rules()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    fn test_game_type() {
+        for variant in Variant::ALL {
+            assert_eq!(Variant::from_game_type(variant.game_type()), Some(variant));
+        }
+
+        assert_eq!(Variant::from_game_type(0), None);
+    }
+}
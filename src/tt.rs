@@ -0,0 +1,310 @@
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+use crate::{BoardSize, CompactMove};
+
+/// How a stored score relates to the real score of a position, see
+/// [`TranspositionEntry::bound`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Bound {
+    /// The score is exact.
+    Exact,
+
+    /// The real score is at least the stored score (the search failed high).
+    Lower,
+
+    /// The real score is at most the stored score (the search failed low).
+    Upper,
+}
+
+/// Search result of a position stored in a [`TranspositionTable`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TranspositionEntry {
+    /// Depth the position was searched to, in plies.
+    pub depth: u8,
+
+    pub bound: Bound,
+
+    pub score: i32,
+
+    /// Best move found, if any. It is only a hint for move ordering, as positions with
+    /// colliding hashes may share an entry.
+    pub best_move: Option<CompactMove>,
+}
+
+/// One slot of the table. `check` holds the key xor the other two words, so entries torn
+/// by concurrent writes don't match their key anymore and are ignored.
+#[derive(Default)]
+struct Slot {
+    check: AtomicU64,
+    best_move: AtomicU64,
+    info: AtomicU64,
+}
+
+/// Bit layout of [`Slot::info`]: the score takes the lowest 32 bits.
+const DEPTH_SHIFT: u32 = 32;
+const BOUND_SHIFT: u32 = 40;
+const GENERATION_SHIFT: u32 = 42;
+const HAS_MOVE: u64 = 1 << 50;
+
+/// Fixed-size table of search results keyed by position hash, e.g.
+/// [`Board::zobrist_with_side`][0].
+///
+/// Every hash maps to a single slot. A new entry replaces the stored one if it is for
+/// the same position, was stored during an older search (see [`Self::new_search`]) or
+/// was searched at least as deep.
+///
+/// The table is lock-free and can be shared across threads, e.g. in an [`Arc`][1] given
+/// to several [`Engine`][2]s with [`Engine::with_table`][3].
+///
+/// # Examples
+///
+/// ```
+/// # use checkers_lib::*;
+/// let table = TranspositionTable::new(1024);
+/// let entry = TranspositionEntry {
+///     depth: 4,
+///     bound: Bound::Exact,
+///     score: 100,
+///     best_move: None,
+/// };
+///
+/// table.store(42, entry);
+///
+/// assert_eq!(table.probe(42, BoardSize::Eight), Some(entry));
+/// assert_eq!(table.probe(43, BoardSize::Eight), None);
+/// ```
+///
+/// [0]: crate::Board::zobrist_with_side
+/// [1]: std::sync::Arc
+/// [2]: crate::Engine
+/// [3]: crate::Engine::with_table
+pub struct TranspositionTable {
+    slots: Box<[Slot]>,
+    generation: AtomicU8,
+}
+
+impl TranspositionTable {
+    /// Creates a table of `len` entries rounded up to a power of two, each taking 24
+    /// bytes.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self {
+            slots: (0..len.max(1).next_power_of_two())
+                .map(|_| Slot::default())
+                .collect(),
+            generation: AtomicU8::new(0),
+        }
+    }
+
+    /// Number of entries of the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Always `false`, a table holds at least one entry.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Returns the entry stored for `hash`, with its best move on a board of `size`.
+    #[must_use]
+    pub fn probe(&self, hash: u64, size: BoardSize) -> Option<TranspositionEntry> {
+        let slot = self.slot(hash);
+        let check = slot.check.load(Ordering::Relaxed);
+        let best_move = slot.best_move.load(Ordering::Relaxed);
+        let info = slot.info.load(Ordering::Relaxed);
+
+        if check ^ best_move ^ info != hash {
+            return None;
+        }
+
+        let bound = match info >> BOUND_SHIFT & 0b11 {
+            1 => Bound::Exact,
+            2 => Bound::Lower,
+            3 => Bound::Upper,
+            _ => return None,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        Some(TranspositionEntry {
+            depth: (info >> DEPTH_SHIFT) as u8,
+            bound,
+            score: info as u32 as i32,
+            best_move: (info & HAS_MOVE != 0)
+                .then(|| CompactMove::decode(best_move, size).ok())
+                .flatten(),
+        })
+    }
+
+    /// Stores `entry` for `hash`, unless the slot holds a deeper entry of another
+    /// position from the current search.
+    pub fn store(&self, hash: u64, entry: TranspositionEntry) {
+        let slot = self.slot(hash);
+        let generation = self.generation.load(Ordering::Relaxed);
+
+        let old_info = slot.info.load(Ordering::Relaxed);
+        let old_hash =
+            slot.check.load(Ordering::Relaxed) ^ slot.best_move.load(Ordering::Relaxed) ^ old_info;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let replace = old_hash == hash
+            || (old_info >> GENERATION_SHIFT) as u8 != generation
+            || (old_info >> DEPTH_SHIFT) as u8 <= entry.depth;
+
+        if !replace {
+            return;
+        }
+
+        let bound: u64 = match entry.bound {
+            Bound::Exact => 1,
+            Bound::Lower => 2,
+            Bound::Upper => 3,
+        };
+
+        #[allow(clippy::cast_sign_loss)]
+        let mut info = u64::from(entry.score as u32)
+            | u64::from(entry.depth) << DEPTH_SHIFT
+            | bound << BOUND_SHIFT
+            | u64::from(generation) << GENERATION_SHIFT;
+        let best_move = entry.best_move.map_or(0, CompactMove::encode);
+
+        if entry.best_move.is_some() {
+            info |= HAS_MOVE;
+        }
+
+        slot.check.store(hash ^ best_move ^ info, Ordering::Relaxed);
+        slot.best_move.store(best_move, Ordering::Relaxed);
+        slot.info.store(info, Ordering::Relaxed);
+    }
+
+    /// Starts a new search, so entries of previous searches get replaced first.
+    pub fn new_search(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Removes all entries.
+    pub fn clear(&self) {
+        for slot in &*self.slots {
+            slot.check.store(0, Ordering::Relaxed);
+            slot.best_move.store(0, Ordering::Relaxed);
+            slot.info.store(0, Ordering::Relaxed);
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn slot(&self, hash: u64) -> &Slot {
+        &self.slots[hash as usize & (self.slots.len() - 1)]
+    }
+}
+
+impl Default for TranspositionTable {
+    /// Table of 65536 entries (1.5 MiB).
+    fn default() -> Self {
+        Self::new(1 << 16)
+    }
+}
+
+impl std::fmt::Debug for TranspositionTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranspositionTable")
+            .field("len", &self.len())
+            .field("generation", &self.generation)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    use crate::{BoardBuilder, MoveList, Variant};
+
+    fn entry(depth: u8, score: i32) -> TranspositionEntry {
+        TranspositionEntry {
+            depth,
+            bound: Bound::Lower,
+            score,
+            best_move: None,
+        }
+    }
+
+    #[test]
+    fn test_store() {
+        let table = TranspositionTable::new(1000);
+        assert_eq!(table.len(), 1024);
+
+        let board = BoardBuilder::initial(Variant::International.rules()).build();
+        let mut list = MoveList::new();
+        board.generate_moves(true, &mut list);
+
+        let stored = TranspositionEntry {
+            depth: 7,
+            bound: Bound::Upper,
+            score: -29_990,
+            best_move: Some(list[3]),
+        };
+        let hash = board.zobrist_with_side(true);
+
+        table.store(hash, stored);
+        assert_eq!(table.probe(hash, BoardSize::Ten), Some(stored));
+        assert_eq!(table.probe(hash ^ 1, BoardSize::Ten), None);
+
+        table.clear();
+        assert_eq!(table.probe(hash, BoardSize::Ten), None);
+    }
+
+    #[test]
+    fn test_replace() {
+        let table = TranspositionTable::new(16);
+
+        // same slot, different positions
+        table.store(1, entry(5, 10));
+        table.store(17, entry(4, 20));
+        assert_eq!(table.probe(1, BoardSize::Eight), Some(entry(5, 10)));
+        assert_eq!(table.probe(17, BoardSize::Eight), None);
+
+        table.store(17, entry(5, 20));
+        assert_eq!(table.probe(17, BoardSize::Eight), Some(entry(5, 20)));
+
+        // same position
+        table.store(17, entry(1, 30));
+        assert_eq!(table.probe(17, BoardSize::Eight), Some(entry(1, 30)));
+
+        // entry of an older search
+        table.store(1, entry(9, 10));
+        table.new_search();
+        table.store(17, entry(0, 20));
+        assert_eq!(table.probe(17, BoardSize::Eight), Some(entry(0, 20)));
+    }
+
+    #[test]
+    fn test_threads() {
+        let table = TranspositionTable::new(64);
+
+        std::thread::scope(|scope| {
+            for thread in 0..4u64 {
+                let table = &table;
+
+                scope.spawn(move || {
+                    for i in 0..10_000u64 {
+                        let hash = i % 256 * 4 + thread;
+
+                        #[allow(clippy::cast_possible_truncation)]
+                        table.store(hash, entry(0, hash as i32));
+
+                        if let Some(probed) = table.probe(hash, BoardSize::Eight) {
+                            #[allow(clippy::cast_possible_truncation)]
+                            let expected = hash as i32;
+                            assert_eq!(probed.score, expected);
+                        }
+                    }
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,49 @@
+//! Zobrist keys used for incremental [`Board`][0] hashing.
+//!
+//! [0]: crate::Board
+
+use crate::{BoardSize, Piece, Position};
+
+const SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+/// Key mixed into the hash when black is to move.
+pub const SIDE: u64 = splitmix64(SEED ^ 0xFFFF_FFFF);
+
+/// Number of squares of the largest supported board.
+const SQUARES: usize = BoardSize::Ten.squares() as usize;
+
+/// Keys for every (piece kind, square) pair, see [`key`].
+const PIECES: [[u64; SQUARES]; 4] = piece_keys();
+
+/// Returns the key of `piece` standing on `pos`.
+#[must_use]
+pub fn key(piece: Piece, pos: Position) -> u64 {
+    let kind = usize::from(piece.is_white) * 2 + usize::from(piece.is_king);
+
+    PIECES[kind][usize::from(u8::from(pos) - 1)]
+}
+
+const fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+const fn piece_keys() -> [[u64; SQUARES]; 4] {
+    let mut keys = [[0; SQUARES]; 4];
+    let mut state = SEED;
+
+    let mut kind = 0;
+    while kind < 4 {
+        let mut square = 0;
+        while square < SQUARES {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            keys[kind][square] = splitmix64(state);
+            square += 1;
+        }
+        kind += 1;
+    }
+
+    keys
+}
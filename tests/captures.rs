@@ -1,6 +1,7 @@
-use std::collections::HashSet;
-
-use checkers_lib::{Board, BoardBuilder, CheckersMove, Piece};
+use checkers_lib::{
+    Board, BoardBuilder, BoardSize, CapturePriority, CapturePromotion, CheckersMove, Piece,
+    Position, Rules, Variant,
+};
 #[allow(unused_imports)]
 use pretty_assertions::{assert_eq, assert_ne};
 
@@ -59,6 +60,22 @@ fn test_backwards_capture() -> Result<(), checkers_lib::Error> {
     Ok(())
 }
 
+#[test]
+fn test_backwards_capture_disallowed() -> Result<(), checkers_lib::Error> {
+    let (board, _) = setup_backwards_capture();
+    let board = board.try_with_rules(Rules {
+        men_capture_backwards: false,
+        ..Rules::default()
+    })?;
+
+    let moves = board.all_possible_moves(true);
+
+    assert_eq!(moves.len(), 2);
+    assert!(moves.iter().all(|i| i.captures().is_empty()));
+
+    Ok(())
+}
+
 fn setup_backwards_capture() -> (Board, Board) {
     let a = BoardBuilder::try_from_template([
         [0, 0, 0, 0, 0, 0, 0, 0],
@@ -148,20 +165,26 @@ fn setup_multiple_capture() -> (Board, (Board, Board), (CheckersMove, CheckersMo
 
     let old = Piece::new(false, true, (2, 5).try_into().unwrap());
 
-    let d = CheckersMove::arbitrary(
+    let d = CheckersMove::arbitrary_with_path(
         old,
         Piece::new(false, true, (6, 1).try_into().unwrap()),
-        HashSet::from([(3, 4).try_into().unwrap(), (5, 2).try_into().unwrap()]),
+        vec![(4, 3).try_into().unwrap(), (6, 1).try_into().unwrap()],
+        vec![(3, 4).try_into().unwrap(), (5, 2).try_into().unwrap()],
     );
 
-    let e = CheckersMove::arbitrary(
+    let e = CheckersMove::arbitrary_with_path(
         old,
         Piece::new(false, true, (0, 3).try_into().unwrap()),
-        HashSet::from([
+        vec![
+            (4, 3).try_into().unwrap(),
+            (2, 1).try_into().unwrap(),
+            (0, 3).try_into().unwrap(),
+        ],
+        vec![
             (3, 4).try_into().unwrap(),
             (3, 2).try_into().unwrap(),
             (1, 2).try_into().unwrap(),
-        ]),
+        ],
     );
 
     (a, (b, c), (d, e))
@@ -213,6 +236,30 @@ fn test_multiple_capture_with_promotion() -> Result<(), checkers_lib::Error> {
     Ok(())
 }
 
+#[test]
+fn test_multiple_capture_promotion_ends() -> Result<(), checkers_lib::Error> {
+    let (board, _, _) = setup_multiple_capture_with_promotion();
+    let board = board.try_with_rules(Rules {
+        capture_promotion: CapturePromotion::End,
+        ..Rules::default()
+    })?;
+
+    let moves = board.all_possible_moves(false);
+
+    assert_eq!(moves.len(), 1);
+    assert_eq!(
+        moves[0],
+        CheckersMove::arbitrary_with_path(
+            Piece::new(false, false, 21.try_into()?),
+            Piece::new(true, false, 30.try_into()?),
+            vec![30.try_into()?],
+            vec![25.try_into()?],
+        )
+    );
+
+    Ok(())
+}
+
 fn setup_multiple_capture_with_promotion() -> (Board, [Board; 3], [CheckersMove; 3]) {
     let a = BoardBuilder::try_from_template([
         [0, 0, 0, 0, 0, 0, 0, 0],
@@ -264,25 +311,272 @@ fn setup_multiple_capture_with_promotion() -> (Board, [Board; 3], [CheckersMove;
     .build();
 
     let old = Piece::new(false, false, 21.try_into().unwrap());
-    let captures = HashSet::from([25.try_into().unwrap(), 23.try_into().unwrap()]);
+    let captures = vec![25.try_into().unwrap(), 23.try_into().unwrap()];
 
-    let e = CheckersMove::arbitrary(
+    let e = CheckersMove::arbitrary_with_path(
         old,
         Piece::new(true, false, 19.try_into().unwrap()),
+        vec![30.try_into().unwrap(), 19.try_into().unwrap()],
         captures.clone(),
     );
 
-    let f = CheckersMove::arbitrary(
+    let f = CheckersMove::arbitrary_with_path(
         old,
         Piece::new(true, false, 16.try_into().unwrap()),
+        vec![30.try_into().unwrap(), 16.try_into().unwrap()],
         captures.clone(),
     );
 
-    let g = CheckersMove::arbitrary(
+    let g = CheckersMove::arbitrary_with_path(
         old,
         Piece::new(true, false, 12.try_into().unwrap()),
+        vec![30.try_into().unwrap(), 12.try_into().unwrap()],
         captures,
     );
 
     (a, [b, c, d], [e, f, g])
 }
+
+#[test]
+fn test_capture_promotion() -> Result<(), checkers_lib::Error> {
+    let board = setup_capture_promotion();
+    let expected = [
+        (CapturePromotion::Continue, "10x3x12", true),
+        (CapturePromotion::PassThrough, "10x3x12", false),
+        (CapturePromotion::End, "10x3", true),
+    ];
+
+    for (capture_promotion, notation, is_king) in expected {
+        let board = board.try_with_rules(Rules {
+            capture_promotion,
+            ..Rules::default()
+        })?;
+        let moves = board.all_possible_moves(true);
+
+        assert_eq!(moves.len(), 1, "{capture_promotion:?}");
+        assert_eq!(format!("{}", moves[0]), notation, "{capture_promotion:?}");
+        assert_eq!(
+            moves[0].new_piece().is_king,
+            is_king,
+            "{capture_promotion:?}"
+        );
+
+        let applied = board.clone().applied_move(&moves[0])?;
+        assert_eq!(
+            applied.get_tile(moves[0].new_piece().position),
+            Some(moves[0].new_piece())
+        );
+    }
+
+    Ok(())
+}
+
+fn setup_capture_promotion() -> Board {
+    BoardBuilder::try_from_template([
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 2, 0, 2, 0],
+        [0, 0, 0, 1, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+    ])
+    .unwrap()
+    .build()
+}
+
+#[test]
+fn test_majority_capture() -> Result<(), checkers_lib::Error> {
+    let board = setup_majority_capture();
+
+    assert_eq!(board.all_possible_moves(true).len(), 2);
+
+    let board = board.try_with_rules(Rules {
+        capture_priority: CapturePriority::Majority,
+        ..Rules::default()
+    })?;
+    let moves = board.all_possible_moves(true);
+
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].old_piece().position, 29.try_into()?);
+    assert_eq!(moves[0].captures().len(), 2);
+
+    Ok(())
+}
+
+fn setup_majority_capture() -> Board {
+    BoardBuilder::try_from_template([
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 2, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 2, 0],
+        [0, 2, 0, 0, 0, 0, 0, 1],
+        [1, 0, 0, 0, 0, 0, 0, 0],
+    ])
+    .unwrap()
+    .build()
+}
+
+#[test]
+fn test_italian_capture_priority() -> Result<(), checkers_lib::Error> {
+    let board = setup_italian_capture_priority();
+
+    assert_eq!(board.all_possible_moves(true).len(), 6);
+
+    let board = board.try_with_rules(Variant::Italian.rules())?;
+    let moves = board.all_possible_moves(true);
+
+    // Both capture one piece, but capturing with a king takes precedence
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].old_piece().position, 32.try_into()?);
+    assert_eq!(moves[0].new_piece().position, 23.try_into()?);
+
+    Ok(())
+}
+
+fn setup_italian_capture_priority() -> Board {
+    BoardBuilder::try_from_template([
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 2, 0, 0, 0, 0, 0, 0],
+        [1, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 2, 0, 0],
+        [0, 0, 0, 0, 0, 0, 3, 0],
+    ])
+    .unwrap()
+    .build()
+}
+
+#[test]
+fn test_men_capture_kings_disallowed() -> Result<(), checkers_lib::Error> {
+    let board = BoardBuilder::empty_with_rules(Variant::Italian.rules())
+        .try_insert(Piece::new(false, true, 22.try_into()?))?
+        .try_insert(Piece::new(true, false, 18.try_into()?))?
+        .build();
+
+    let moves = board.all_possible_moves(true);
+
+    assert!(moves.iter().all(|i| i.captures().is_empty()));
+    assert_eq!(board.all_possible_moves(false)[0].captures().len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_turkish_strike() -> Result<(), checkers_lib::Error> {
+    let board = setup_turkish_strike();
+    let moves = board.all_possible_moves(true);
+
+    // Captured 22 still blocks the way from 31 to 17 until the capture ends
+    let longest = moves.iter().max_by_key(|i| i.captures().len()).unwrap();
+    assert_eq!(format!("{longest}"), "29x15x24x31");
+    let blocked = 17.try_into()?;
+    assert!(moves.iter().all(|i| !i.captures().contains(&blocked)));
+
+    Ok(())
+}
+
+#[test]
+fn test_king_must_continue() -> Result<(), checkers_lib::Error> {
+    let board = setup_turkish_strike();
+    let moves = board.all_possible_moves(true);
+
+    // After capturing 22 the king can continue from 18 and 15, so it can't stop on 11,
+    // 8 or 4, and after capturing 19 it has to land on 24 rather than 28
+    let mut notations = moves.iter().map(|i| format!("{i}")).collect::<Vec<_>>();
+    notations.sort();
+
+    assert_eq!(notations, ["29x15x24x31", "29x18x32"]);
+
+    Ok(())
+}
+
+fn setup_turkish_strike() -> Board {
+    BoardBuilder::try_from_template([
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 2, 0, 0, 0, 2, 0, 0],
+        [0, 0, 2, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 2, 0, 0],
+        [3, 0, 0, 0, 0, 0, 0, 0],
+    ])
+    .unwrap()
+    .build()
+}
+
+#[test]
+fn test_capture_routes() -> Result<(), checkers_lib::Error> {
+    let (board, board_result) = setup_capture_routes();
+    let moves = board.all_possible_moves(true);
+
+    assert_eq!(moves.len(), 2);
+    assert_eq!(moves[0].captures(), moves[1].captures());
+    assert_eq!(moves[0].new_piece(), moves[1].new_piece());
+    assert_ne!(moves[0].path(), moves[1].path());
+    assert_ne!(moves[0], moves[1]);
+
+    for capture in moves {
+        assert_eq!(capture.path().len(), 4);
+        assert_eq!(capture.captures_in_order().len(), 4);
+
+        assert_eq!(board.clone().applied_move(&capture)?, board_result);
+        assert_eq!(board.decode_move(board.encode_move(&capture)?)?, capture);
+    }
+
+    Ok(())
+}
+
+fn setup_capture_routes() -> (Board, Board) {
+    let a = BoardBuilder::try_from_template([
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 2, 0, 2, 0, 0],
+        [0, 0, 1, 0, 0, 0, 0, 0],
+        [0, 0, 0, 2, 0, 2, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+    ])
+    .unwrap()
+    .build();
+    let b = BoardBuilder::try_from_template([
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 1, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [0, 0, 0, 0, 0, 0, 0, 0],
+    ])
+    .unwrap()
+    .build();
+
+    (a, b)
+}
+
+#[test]
+fn test_debug_format() -> Result<(), checkers_lib::Error> {
+    let board = Board::default().try_with_rules(Variant::International.rules())?;
+    for capture in board.all_possible_moves(true) {
+        assert!(format!("{capture:?}").contains("0 captures"));
+    }
+
+    let size = BoardSize::default();
+    let old = Piece::new(true, true, Position::try_new(23, size)?);
+    let new = Piece::new(true, true, Position::try_new(32, size)?);
+    let capture = CheckersMove::arbitrary(old, new, [Position::try_new(27, size)?].into());
+    assert!(format!("{capture:?}").contains("1 captures"));
+
+    let capture = CheckersMove::arbitrary(old, new, [Position::try_new(30, size)?].into());
+    assert!(format!("{capture:?}").contains("1 captures"));
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use checkers_lib::{Board, BoardBuilder, Variant};
+#[allow(unused_imports)]
+use pretty_assertions::{assert_eq, assert_ne};
+
+/// Russian draughts node counts from the initial position, white to move.
+const RUSSIAN: [u64; 8] = [1, 7, 49, 302, 1469, 7482, 37986, 190146];
+
+/// English draughts node counts from the initial position, black to move.
+const ENGLISH: [u64; 9] = [1, 7, 49, 302, 1469, 7361, 36768, 179740, 845931];
+
+/// Brazilian draughts node counts from the initial position, white to move.
+const BRAZILIAN: [u64; 8] = [1, 7, 49, 302, 1469, 7473, 37628, 187302];
+
+/// Italian draughts node counts from the initial position, white to move.
+const ITALIAN: [u64; 8] = [1, 7, 49, 302, 1469, 7361, 36473, 177532];
+
+/// International draughts node counts from the initial position, white to move.
+const INTERNATIONAL: [u64; 7] = [1, 9, 81, 658, 4265, 27117, 167140];
+
+fn setup_initial() -> Board {
+    BoardBuilder::default().build()
+}
+
+#[test]
+fn test_perft_russian() {
+    let board = setup_initial();
+
+    for (depth, nodes) in RUSSIAN.into_iter().enumerate() {
+        assert_eq!(board.perft(depth as u32, true), nodes, "depth {depth}");
+    }
+}
+
+#[test]
+fn test_perft_english() {
+    let board = BoardBuilder::initial(Variant::English.rules()).build();
+
+    for (depth, nodes) in ENGLISH.into_iter().enumerate() {
+        assert_eq!(board.perft(depth as u32, false), nodes, "depth {depth}");
+    }
+}
+
+#[test]
+fn test_perft_international() {
+    let board = BoardBuilder::initial(Variant::International.rules()).build();
+
+    for (depth, nodes) in INTERNATIONAL.into_iter().enumerate() {
+        assert_eq!(board.perft(depth as u32, true), nodes, "depth {depth}");
+    }
+}
+
+#[test]
+fn test_perft_brazilian() {
+    let board = BoardBuilder::initial(Variant::Brazilian.rules()).build();
+
+    for (depth, nodes) in BRAZILIAN.into_iter().enumerate() {
+        assert_eq!(board.perft(depth as u32, true), nodes, "depth {depth}");
+    }
+}
+
+#[test]
+fn test_perft_italian() {
+    let board = BoardBuilder::initial(Variant::Italian.rules()).build();
+
+    for (depth, nodes) in ITALIAN.into_iter().enumerate() {
+        assert_eq!(board.perft(depth as u32, true), nodes, "depth {depth}");
+    }
+}
+
+#[test]
+fn test_perft_divide() {
+    let board = setup_initial();
+
+    for depth in 1..5 {
+        let divide = board.perft_divide(depth, true);
+
+        assert_eq!(divide.moves.len(), 7);
+        assert_eq!(divide.total(), RUSSIAN[depth as usize]);
+
+        for (checkers_move, nodes) in &divide.moves {
+            let next = board.clone().applied_move(checkers_move).unwrap();
+            assert_eq!(next.perft(depth - 1, false), *nodes);
+        }
+    }
+}
+
+#[test]
+fn test_perft_divide_output() {
+    let divide = setup_initial().perft_divide(2, true);
+
+    assert_eq!(
+        divide.to_string(),
+        "21-17: 7\n22-17: 7\n22-18: 7\n23-18: 7\n23-19: 7\n24-19: 7\n24-20: 7\ntotal: 49"
+    );
+}
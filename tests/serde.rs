@@ -0,0 +1,87 @@
+#![cfg(feature = "serde")]
+
+use checkers_lib::{Board, BoardBuilder, CheckersMove, Piece, Position, Variant};
+#[allow(unused_imports)]
+use pretty_assertions::{assert_eq, assert_ne};
+
+#[test]
+fn test_position() -> Result<(), Box<dyn std::error::Error>> {
+    let position: Position = 22.try_into()?;
+
+    assert_eq!(serde_json::to_string(&position)?, "22");
+    assert_eq!(serde_json::from_str::<Position>("22")?, position);
+    serde_json::from_str::<Position>("33").expect_err("deserialized out of bounds position");
+
+    Ok(())
+}
+
+#[test]
+fn test_piece() -> Result<(), Box<dyn std::error::Error>> {
+    let piece = Piece::new(true, false, 9.try_into()?);
+    let json = serde_json::to_string(&piece)?;
+
+    assert_eq!(json, r#"{"is_king":true,"is_white":false,"position":9}"#);
+    assert_eq!(serde_json::from_str::<Piece>(&json)?, piece);
+
+    Ok(())
+}
+
+#[test]
+fn test_board() -> Result<(), Box<dyn std::error::Error>> {
+    for variant in Variant::ALL {
+        let board = BoardBuilder::initial(variant.rules()).build();
+        let json = serde_json::to_string(&board)?;
+
+        assert_eq!(serde_json::from_str::<Board>(&json)?, board);
+        assert_eq!(
+            serde_json::from_str::<BoardBuilder>(&json)?,
+            BoardBuilder::initial(variant.rules())
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_board() -> Result<(), Box<dyn std::error::Error>> {
+    let rules = serde_json::to_string(&Variant::Russian.rules())?;
+    let board = |pieces: &str| format!(r#"{{"rules":{rules},"pieces":[{pieces}]}}"#);
+
+    serde_json::from_str::<Board>(&board(
+        r#"{"is_king":false,"is_white":true,"position":22},
+           {"is_king":false,"is_white":false,"position":22}"#,
+    ))
+    .expect_err("deserialized board with two pieces on a square");
+    serde_json::from_str::<Board>(&board(r#"{"is_king":false,"is_white":true,"position":1}"#))
+        .expect_err("deserialized man on promoting square");
+    serde_json::from_str::<Board>(&board(r#"{"is_king":false,"is_white":true,"position":50}"#))
+        .expect_err("deserialized out of bounds piece");
+
+    Ok(())
+}
+
+#[test]
+fn test_move() -> Result<(), Box<dyn std::error::Error>> {
+    let (board, _) = Board::from_fen("W:W22:B18,11")?;
+    let capture = &board.legal_moves(true)[0];
+    let json = serde_json::to_string(capture)?;
+
+    assert_eq!(serde_json::from_str::<CheckersMove>(&json)?, *capture);
+
+    let board = BoardBuilder::initial(Variant::International.rules()).build();
+
+    for m in board.legal_moves(true) {
+        let json = serde_json::to_string(&m)?;
+
+        assert_eq!(serde_json::from_str::<CheckersMove>(&json)?, m);
+    }
+
+    serde_json::from_str::<CheckersMove>(
+        r#"{"old":{"is_king":false,"is_white":true,"position":22},
+            "new":{"is_king":false,"is_white":true,"position":18},
+            "path":[17],"captures":[]}"#,
+    )
+    .expect_err("deserialized move with path not ending on new piece");
+
+    Ok(())
+}